@@ -0,0 +1,147 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for [`crate::WeightInfo`], run against the proof the verifier is tested with.
+//!
+//! Generate the weights of a chain with its benchmarking CLI, e.g.
+//! `frame-omni-bencher v1 benchmark pallet --runtime <runtime.wasm> --pallet sp1_zkv_pallet_adapter
+//! --extrinsic "*" --output weights.rs`, and set the resulting type as the `WeightInfo` of
+//! [`crate::Config`].
+
+use crate::{Pubs, Sp1, Verifier};
+use alloc::vec;
+use core::hint::black_box;
+use frame_benchmarking::v2::*;
+use frame_support::BoundedVec;
+use hex_literal::hex;
+use p3_baby_bear::BabyBear;
+use p3_field::{AbstractExtensionField, AbstractField, extension::BinomialExtensionField};
+use sp1_zkv_verifier::{HashBackend, NativeBackend, POSEIDON2_WIDTH, Proof};
+
+pub use pallet::*;
+
+/// A pallet without calls nor storage, only there to host the benchmarks.
+#[frame_support::pallet]
+pub mod pallet {
+    #[pallet::config]
+    pub trait Config: frame_system::Config + crate::Config {}
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+}
+
+const VK: [u8; 32] = hex!("45946758049372c74bceb6ba6526a9661b1915a3403125e470d5ff7f6c15c0dd");
+const PUBS: [u8; 32] = hex!("b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9");
+
+fn proof() -> Proof {
+    bincode::serde::decode_from_slice(
+        include_bytes!("../../sp1-zkv-verifier/resources/proof.bin"),
+        bincode::config::legacy(),
+    )
+    .unwrap()
+    .0
+}
+
+fn pubs<T: crate::Config>(len: usize) -> Pubs<T> {
+    BoundedVec::truncate_from(PUBS.iter().copied().cycle().take(len).collect())
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn validate_vk() {
+        #[block]
+        {
+            assert!(Sp1::<T>::validate_vk(&VK).is_ok());
+        }
+    }
+
+    #[benchmark]
+    fn validate_size() {
+        let proof = proof();
+        let pubs = pubs::<T>(PUBS.len());
+
+        #[block]
+        {
+            assert!(Sp1::<T>::validate_size(&proof, &pubs).is_ok());
+        }
+    }
+
+    #[benchmark]
+    fn pubs_hash(l: Linear<0, 4096>) {
+        let pubs = pubs::<T>(l as usize);
+
+        #[block]
+        {
+            black_box(Sp1::<T>::pubs_hash(&pubs));
+        }
+    }
+
+    #[benchmark]
+    fn poseidon2_permutations(n: Linear<1, 10_000>) {
+        let backend = NativeBackend::default();
+        let mut state = [BabyBear::zero(); POSEIDON2_WIDTH];
+
+        #[block]
+        {
+            for _ in 0..n {
+                backend.poseidon2_permute(&mut state);
+            }
+        }
+        black_box(state);
+    }
+
+    #[benchmark]
+    fn field_ops(n: Linear<1, 100_000>) {
+        // Multiplications in the extension field, the most expensive of the counted operations.
+        let x = black_box(BinomialExtensionField::<BabyBear, 4>::from_base_fn(|i| {
+            BabyBear::from_canonical_usize(i + 2)
+        }));
+        let mut acc = x;
+
+        #[block]
+        {
+            for _ in 0..n {
+                acc *= x;
+            }
+        }
+        black_box(acc);
+    }
+
+    #[benchmark]
+    fn sha256_blocks(n: Linear<1, 1_000>) {
+        let backend = NativeBackend::default();
+        // Leave room for the 9 padding bytes of the last block.
+        let data = vec![0u8; 64 * n as usize - 9];
+
+        #[block]
+        {
+            black_box(backend.sha256(&data));
+        }
+    }
+
+    #[benchmark]
+    fn blake3_chunks(n: Linear<1, 1_000>) {
+        let backend = NativeBackend::default();
+        let data = vec![0u8; 1024 * n as usize];
+
+        #[block]
+        {
+            black_box(backend.blake3(&data));
+        }
+    }
+}
@@ -0,0 +1,174 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Glue needed to plug [`sp1_zkv_verifier`] into a zkVerify-style verification pallet.
+//!
+//! [`Verifier`] is the interface such pallets expect from a proof system, and [`Sp1`] implements
+//! it for SP1 shrink proofs.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+mod weight;
+
+use alloc::borrow::Cow;
+use core::marker::PhantomData;
+use frame_support::{BoundedVec, pallet_prelude::Get, weights::Weight};
+use sp_core::H256;
+use sp_crypto_hashing::keccak_256;
+use sp1_zkv_verifier::{Error, PUBLIC_INPUTS_MISMATCH};
+pub use weight::{WeightInfo, verify_proof_weight};
+
+/// Error returned by a [`Verifier`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerifyError {
+    /// The public inputs are malformed, or aren't the ones the proof is about.
+    InvalidInput,
+    /// The proof is malformed or exceeds the configured limits.
+    InvalidProofData,
+    /// The proof doesn't verify.
+    VerifyError,
+    /// The verification key is malformed.
+    InvalidVerificationKey,
+}
+
+/// A proof system as seen by a verification pallet.
+pub trait Verifier {
+    /// The proof.
+    type Proof;
+    /// The public inputs.
+    type Pubs;
+    /// The verification key.
+    type Vk;
+
+    /// Tag identifying the proof system in statement hashes.
+    fn hash_context_data() -> &'static [u8];
+
+    /// Verify `proof` against `vk` and `pubs`. On success, optionally return the weight actually
+    /// used when it is lower than the one charged up front.
+    fn verify_proof(
+        vk: &Self::Vk,
+        proof: &Self::Proof,
+        pubs: &Self::Pubs,
+    ) -> Result<Option<Weight>, VerifyError>;
+
+    /// Check that `vk` is well formed, before registering it.
+    fn validate_vk(vk: &Self::Vk) -> Result<(), VerifyError>;
+
+    /// Check that `proof` and `pubs` are within the limits the chain is willing to verify.
+    fn validate_size(proof: &Self::Proof, pubs: &Self::Pubs) -> Result<(), VerifyError>;
+
+    /// Canonical bytes of `vk`.
+    fn vk_bytes(vk: &Self::Vk) -> Cow<'_, [u8]>;
+
+    /// Canonical bytes of `pubs`.
+    fn pubs_bytes(pubs: &Self::Pubs) -> Cow<'_, [u8]>;
+
+    /// Hash identifying `vk`.
+    fn vk_hash(vk: &Self::Vk) -> H256 {
+        H256(keccak_256(&Self::vk_bytes(vk)))
+    }
+
+    /// Hash identifying `pubs`.
+    fn pubs_hash(pubs: &Self::Pubs) -> H256 {
+        H256(keccak_256(&Self::pubs_bytes(pubs)))
+    }
+}
+
+/// Limits a chain puts on the SP1 proofs it accepts.
+pub trait Config {
+    /// Maximum length of the public inputs, in bytes.
+    type MaxPubsSize: Get<u32>;
+    /// Maximum depth of the recursion vk Merkle proof.
+    type MaxVkMerkleDepth: Get<u32>;
+    /// Maximum number of Poseidon2 permutations a single verification may need.
+    type MaxPoseidon2Permutations: Get<u64>;
+    /// Weights of the verification primitives.
+    type WeightInfo: WeightInfo;
+}
+
+/// Public inputs of an SP1 proof, bounded by the chain configuration.
+pub type Pubs<T> = BoundedVec<u8, <T as Config>::MaxPubsSize>;
+
+/// The SP1 [`Verifier`].
+pub struct Sp1<T>(PhantomData<T>);
+
+impl<T: Config> Verifier for Sp1<T> {
+    type Proof = sp1_zkv_verifier::Proof;
+    type Pubs = Pubs<T>;
+    type Vk = [u8; 32];
+
+    fn hash_context_data() -> &'static [u8] {
+        b"sp1"
+    }
+
+    fn verify_proof(
+        vk: &Self::Vk,
+        proof: &Self::Proof,
+        pubs: &Self::Pubs,
+    ) -> Result<Option<Weight>, VerifyError> {
+        Self::validate_size(proof, pubs)?;
+        sp1_zkv_verifier::verify(vk, proof, pubs).map_err(|error| match error {
+            Error::InvalidPublicValues(PUBLIC_INPUTS_MISMATCH) => VerifyError::InvalidInput,
+            _ => VerifyError::VerifyError,
+        })?;
+        Ok(None)
+    }
+
+    fn validate_vk(_vk: &Self::Vk) -> Result<(), VerifyError> {
+        // Any 32 bytes are a possible SP1 program vk digest.
+        Ok(())
+    }
+
+    fn validate_size(proof: &Self::Proof, pubs: &Self::Pubs) -> Result<(), VerifyError> {
+        if proof.vk_merkle_proof.path.len() > T::MaxVkMerkleDepth::get() as usize {
+            return Err(VerifyError::InvalidProofData);
+        }
+        let cost = sp1_zkv_verifier::estimate_cost(proof, pubs.len())
+            .map_err(|_| VerifyError::InvalidProofData)?;
+        if cost.poseidon2_permutations > T::MaxPoseidon2Permutations::get() {
+            return Err(VerifyError::InvalidProofData);
+        }
+        Ok(())
+    }
+
+    fn vk_bytes(vk: &Self::Vk) -> Cow<'_, [u8]> {
+        Cow::Borrowed(vk)
+    }
+
+    fn pubs_bytes(pubs: &Self::Pubs) -> Cow<'_, [u8]> {
+        Cow::Borrowed(pubs)
+    }
+
+    /// The vk is already a digest of the SP1 program, so it is its own hash.
+    fn vk_hash(vk: &Self::Vk) -> H256 {
+        H256(*vk)
+    }
+}
+
+impl<T: Config> Sp1<T> {
+    /// Weight to charge before verifying `proof` with `pubs`, from what verifying this very proof
+    /// costs.
+    pub fn verify_proof_weight(proof: &sp1_zkv_verifier::Proof, pubs: &Pubs<T>) -> Weight {
+        match sp1_zkv_verifier::estimate_cost(proof, pubs.len()) {
+            Ok(cost) => verify_proof_weight::<T::WeightInfo>(&cost),
+            // Such a proof fails `validate_size` before anything gets verified.
+            Err(_) => T::WeightInfo::validate_size(),
+        }
+    }
+}
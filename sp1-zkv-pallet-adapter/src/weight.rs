@@ -0,0 +1,77 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support::weights::Weight;
+use sp1_zkv_verifier::VerificationCost;
+
+/// Weights of the primitives of an SP1 verification, as measured by the benchmarks in
+/// [`crate::benchmarking`].
+pub trait WeightInfo {
+    /// Checking that a vk is well formed.
+    fn validate_vk() -> Weight;
+    /// Checking that a proof is within the configured limits.
+    fn validate_size() -> Weight;
+    /// Hashing `l` bytes of public inputs.
+    fn pubs_hash(l: u32) -> Weight;
+    /// Running `n` Poseidon2 permutations.
+    fn poseidon2_permutations(n: u32) -> Weight;
+    /// Running `n` operations in the extension field of BabyBear.
+    fn field_ops(n: u32) -> Weight;
+    /// Hashing `n` SHA-256 blocks.
+    fn sha256_blocks(n: u32) -> Weight;
+    /// Hashing `n` BLAKE3 chunks.
+    fn blake3_chunks(n: u32) -> Weight;
+}
+
+/// Weight of a verification costing `cost`, as estimated by [`sp1_zkv_verifier::estimate_cost`]:
+/// the benchmarked weight of each of the primitives it's made of.
+pub fn verify_proof_weight<W: WeightInfo>(cost: &VerificationCost) -> Weight {
+    let saturate = |n: u64| u32::try_from(n).unwrap_or(u32::MAX);
+    W::poseidon2_permutations(saturate(cost.poseidon2_permutations))
+        .saturating_add(W::field_ops(saturate(cost.field_ops)))
+        .saturating_add(W::sha256_blocks(saturate(cost.sha256_blocks)))
+        .saturating_add(W::blake3_chunks(saturate(cost.blake3_chunks)))
+}
+
+/// No weight at all, for tests and mocks.
+impl WeightInfo for () {
+    fn validate_vk() -> Weight {
+        Weight::zero()
+    }
+
+    fn validate_size() -> Weight {
+        Weight::zero()
+    }
+
+    fn pubs_hash(_l: u32) -> Weight {
+        Weight::zero()
+    }
+
+    fn poseidon2_permutations(_n: u32) -> Weight {
+        Weight::zero()
+    }
+
+    fn field_ops(_n: u32) -> Weight {
+        Weight::zero()
+    }
+
+    fn sha256_blocks(_n: u32) -> Weight {
+        Weight::zero()
+    }
+
+    fn blake3_chunks(_n: u32) -> Weight {
+        Weight::zero()
+    }
+}
@@ -0,0 +1,209 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support::{
+    BoundedVec,
+    traits::{ConstU32, ConstU64},
+    weights::Weight,
+};
+use hex_literal::hex;
+use rstest::{fixture, rstest};
+use sp_core::H256;
+use sp1_zkv_pallet_adapter::{
+    Config, Pubs, Sp1, Verifier, VerifyError, WeightInfo, verify_proof_weight,
+};
+use sp1_zkv_verifier::{Proof, VerificationCost, estimate_cost};
+
+struct Test<const DEPTH: u32 = 32, const PERMUTATIONS: u64 = 1_000_000>;
+
+impl<const DEPTH: u32, const PERMUTATIONS: u64> Config for Test<DEPTH, PERMUTATIONS> {
+    type MaxPubsSize = ConstU32<1024>;
+    type MaxVkMerkleDepth = ConstU32<DEPTH>;
+    type MaxPoseidon2Permutations = ConstU64<PERMUTATIONS>;
+    type WeightInfo = ();
+}
+
+const VK: [u8; 32] = hex!("45946758049372c74bceb6ba6526a9661b1915a3403125e470d5ff7f6c15c0dd");
+const INPUTS: [u8; 32] = hex!("b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9");
+
+#[fixture]
+fn valid_proof() -> Proof {
+    bincode::serde::decode_from_std_read(
+        &mut std::fs::File::open("../sp1-zkv-verifier/resources/proof.bin").unwrap(),
+        bincode::config::legacy(),
+    )
+    .unwrap()
+}
+
+fn pubs<T: Config>(inputs: &[u8]) -> Pubs<T> {
+    BoundedVec::try_from(inputs.to_vec()).unwrap()
+}
+
+#[rstest]
+fn accept_valid_proof(valid_proof: Proof) {
+    assert_eq!(
+        Sp1::<Test>::verify_proof(&VK, &valid_proof, &pubs::<Test>(&INPUTS)),
+        Ok(None)
+    );
+}
+
+#[rstest]
+fn reject_proof_with_invalid_vk(valid_proof: Proof) {
+    let mut vk = VK;
+    vk[0] ^= 1;
+
+    assert_eq!(
+        Sp1::<Test>::verify_proof(&vk, &valid_proof, &pubs::<Test>(&INPUTS)),
+        Err(VerifyError::VerifyError)
+    );
+}
+
+#[rstest]
+fn reject_proof_with_invalid_pubs(valid_proof: Proof) {
+    let mut inputs = INPUTS;
+    inputs[0] ^= 1;
+
+    assert_eq!(
+        Sp1::<Test>::verify_proof(&VK, &valid_proof, &pubs::<Test>(&inputs)),
+        Err(VerifyError::InvalidInput)
+    );
+}
+
+#[rstest]
+fn reject_too_deep_vk_merkle_proof(valid_proof: Proof) {
+    assert_eq!(
+        Sp1::<Test<0>>::validate_size(&valid_proof, &pubs::<Test<0>>(&INPUTS)),
+        Err(VerifyError::InvalidProofData)
+    );
+}
+
+#[rstest]
+fn reject_too_expensive_proof(valid_proof: Proof) {
+    assert_eq!(
+        Sp1::<Test<32, 1>>::verify_proof(&VK, &valid_proof, &pubs::<Test<32, 1>>(&INPUTS)),
+        Err(VerifyError::InvalidProofData)
+    );
+}
+
+#[test]
+fn use_vk_digest_as_vk_hash() {
+    assert_eq!(Sp1::<Test>::vk_hash(&VK), H256(VK));
+}
+
+#[test]
+fn hash_pubs_with_keccak() {
+    assert_eq!(
+        Sp1::<Test>::pubs_hash(&pubs::<Test>(&INPUTS)),
+        H256(sp_crypto_hashing::keccak_256(&INPUTS))
+    );
+}
+
+#[rstest]
+fn charge_no_weight_with_unit_weight_info(valid_proof: Proof) {
+    assert_eq!(
+        Sp1::<Test>::verify_proof_weight(&valid_proof, &pubs::<Test>(&INPUTS)),
+        Weight::zero()
+    );
+}
+
+struct UnitWeights;
+
+impl WeightInfo for UnitWeights {
+    fn validate_vk() -> Weight {
+        Weight::from_parts(1, 0)
+    }
+
+    fn validate_size() -> Weight {
+        Weight::from_parts(7, 0)
+    }
+
+    fn pubs_hash(l: u32) -> Weight {
+        Weight::from_parts(l as u64, 0)
+    }
+
+    fn poseidon2_permutations(n: u32) -> Weight {
+        Weight::from_parts(100 * n as u64, 0)
+    }
+
+    fn field_ops(n: u32) -> Weight {
+        Weight::from_parts(n as u64, 0)
+    }
+
+    fn sha256_blocks(n: u32) -> Weight {
+        Weight::from_parts(10 * n as u64, 0)
+    }
+
+    fn blake3_chunks(n: u32) -> Weight {
+        Weight::from_parts(1_000 * n as u64, 0)
+    }
+}
+
+struct Weighted;
+
+impl Config for Weighted {
+    type MaxPubsSize = ConstU32<1024>;
+    type MaxVkMerkleDepth = ConstU32<32>;
+    type MaxPoseidon2Permutations = ConstU64<1_000_000>;
+    type WeightInfo = UnitWeights;
+}
+
+#[test]
+fn weigh_each_primitive_of_the_cost() {
+    let cost = VerificationCost {
+        poseidon2_permutations: 2,
+        field_ops: 3,
+        sha256_blocks: 4,
+        blake3_chunks: 5,
+    };
+    assert_eq!(
+        verify_proof_weight::<UnitWeights>(&cost),
+        Weight::from_parts(200 + 3 + 40 + 5_000, 0)
+    );
+}
+
+#[rstest]
+fn charge_the_estimated_cost_of_the_proof(valid_proof: Proof) {
+    let cost = estimate_cost(&valid_proof, INPUTS.len()).unwrap();
+    assert_eq!(
+        Sp1::<Weighted>::verify_proof_weight(&valid_proof, &pubs::<Weighted>(&INPUTS)),
+        verify_proof_weight::<UnitWeights>(&cost)
+    );
+}
+
+#[rstest]
+fn charge_more_for_longer_pubs_and_deeper_vk_merkle_path(mut valid_proof: Proof) {
+    let weight = Sp1::<Weighted>::verify_proof_weight(&valid_proof, &pubs::<Weighted>(&INPUTS));
+    assert!(
+        Sp1::<Weighted>::verify_proof_weight(&valid_proof, &pubs::<Weighted>(&[0; 1024]))
+            .ref_time()
+            > weight.ref_time()
+    );
+
+    let node = valid_proof.vk_merkle_proof.path[0];
+    valid_proof.vk_merkle_proof.path.extend([node; 2]);
+    assert!(
+        Sp1::<Weighted>::verify_proof_weight(&valid_proof, &pubs::<Weighted>(&INPUTS)).ref_time()
+            > weight.ref_time()
+    );
+}
+
+#[rstest]
+fn charge_the_size_check_of_proofs_it_cannot_estimate(mut valid_proof: Proof) {
+    valid_proof.vk.chip_information[0].0.push('x');
+    assert_eq!(
+        Sp1::<Weighted>::verify_proof_weight(&valid_proof, &pubs::<Weighted>(&INPUTS)),
+        UnitWeights::validate_size()
+    );
+}
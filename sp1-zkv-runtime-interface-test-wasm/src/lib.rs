@@ -0,0 +1,50 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A wasm runtime exporting the verification entry points of [`sp1_zkv_runtime_interface`], so
+//! that tests can call them through a real wasm executor.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
+include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+#[cfg(not(feature = "std"))]
+use {
+    alloc::vec::Vec,
+    sp1_zkv_runtime_interface::{VerifyError, sp1_verify, verify_in_runtime},
+};
+
+#[cfg(not(feature = "std"))]
+sp_core::wasm_export_functions! {
+    fn test_verify(
+        vkey_digest: [u8; 32],
+        proof: Vec<u8>,
+        inputs: Vec<u8>,
+    ) -> Result<(), VerifyError> {
+        sp1_verify::verify(&vkey_digest, &proof, &inputs)
+    }
+
+    fn test_verify_in_runtime(
+        vkey_digest: [u8; 32],
+        proof: Vec<u8>,
+        inputs: Vec<u8>,
+    ) -> Result<(), VerifyError> {
+        verify_in_runtime(&vkey_digest, &proof, &inputs)
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runs [`sp1_zkv_verifier::verify`] natively on a Substrate host.
+//!
+//! A wasm runtime calls [`sp1_verify::verify`], which crosses into the host where the whole
+//! shrink STARK verification runs natively. Runtimes that cannot rely on the host function being
+//! available use [`verify_in_runtime`] instead, which runs the same verification in wasm.
+//! Proofs cross the boundary as the legacy bincode encoding of [`sp1_zkv_verifier::Proof`].
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use sp_runtime_interface::{
+    pass_by::{AllocateAndReturnByCodec, PassFatPointerAndRead, PassPointerAndRead},
+    runtime_interface,
+};
+use sp1_zkv_verifier::{Error, Proof};
+
+/// Error returned by the verification, on both sides of the runtime boundary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub enum VerifyError {
+    /// The proof bytes are not a valid encoding of a proof, or the verifier panicked on the proof
+    /// they encode.
+    InvalidProofData,
+    /// The proof is about another program vk or other public inputs, or its recursion public
+    /// values are inconsistent.
+    InvalidPublicValues,
+    /// The recursion vk of the proof is not a member of the recursion vk root.
+    InvalidVerificationKey,
+    /// The shrink STARK proof is invalid.
+    InvalidProof,
+}
+
+impl From<Error> for VerifyError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::InvalidPublicValues(_) => VerifyError::InvalidPublicValues,
+            Error::InvalidVerificationKey => VerifyError::InvalidVerificationKey,
+            _ => VerifyError::InvalidProof,
+        }
+    }
+}
+
+#[runtime_interface]
+pub trait Sp1Verify {
+    /// Verify `proof` against `vkey_digest` and `inputs` natively on the host.
+    fn verify(
+        vkey_digest: PassPointerAndRead<&[u8; 32], 32>,
+        proof: PassFatPointerAndRead<&[u8]>,
+        inputs: PassFatPointerAndRead<&[u8]>,
+    ) -> AllocateAndReturnByCodec<Result<(), VerifyError>> {
+        // Malformed proofs must fail their verification, not take the node down.
+        std::panic::catch_unwind(|| verify_in_runtime(vkey_digest, proof, inputs))
+            .unwrap_or(Err(VerifyError::InvalidProofData))
+    }
+}
+
+/// Verify `proof` against `vkey_digest` and `inputs` without leaving the runtime.
+///
+/// Unlike the host function, a verifier panic on a malformed proof aborts the wasm runtime.
+pub fn verify_in_runtime(
+    vkey_digest: &[u8; 32],
+    proof: &[u8],
+    inputs: &[u8],
+) -> Result<(), VerifyError> {
+    let proof = decode_proof(proof)?;
+    sp1_zkv_verifier::verify(vkey_digest, &proof, inputs).map_err(VerifyError::from)
+}
+
+/// Decode a proof from the encoding used across the runtime boundary, which must be all of
+/// `proof`.
+pub fn decode_proof(proof: &[u8]) -> Result<Proof, VerifyError> {
+    match bincode::serde::decode_from_slice(proof, bincode::config::legacy()) {
+        Ok((decoded, read)) if read == proof.len() => Ok(decoded),
+        _ => Err(VerifyError::InvalidProofData),
+    }
+}
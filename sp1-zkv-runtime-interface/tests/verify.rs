@@ -0,0 +1,101 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use rstest::{fixture, rstest};
+use sp1_zkv_runtime_interface::{VerifyError, decode_proof, sp1_verify, verify_in_runtime};
+
+#[fixture]
+fn valid_proof() -> Vec<u8> {
+    std::fs::read("../sp1-zkv-verifier/resources/proof.bin").unwrap()
+}
+
+#[fixture]
+fn valid_vk_hash() -> [u8; 32] {
+    hex::decode("45946758049372c74bceb6ba6526a9661b1915a3403125e470d5ff7f6c15c0dd")
+        .unwrap()
+        .try_into()
+        .unwrap()
+}
+
+#[fixture]
+fn valid_inputs() -> Vec<u8> {
+    hex::decode("b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9").unwrap()
+}
+
+fn flip_first_bit(mut bytes: Vec<u8>) -> Vec<u8> {
+    bytes[0] ^= 1;
+    bytes
+}
+
+#[rstest]
+#[case::valid(valid_vk_hash(), valid_proof(), valid_inputs(), Ok(()))]
+#[case::invalid_vk_hash(
+    flip_first_bit(valid_vk_hash().to_vec()).try_into().unwrap(),
+    valid_proof(),
+    valid_inputs(),
+    Err(VerifyError::InvalidPublicValues)
+)]
+#[case::invalid_inputs(
+    valid_vk_hash(),
+    valid_proof(),
+    flip_first_bit(valid_inputs()),
+    Err(VerifyError::InvalidPublicValues)
+)]
+#[case::truncated_proof(
+    valid_vk_hash(),
+    valid_proof()[..100].to_vec(),
+    valid_inputs(),
+    Err(VerifyError::InvalidProofData)
+)]
+#[case::trailing_bytes(
+    valid_vk_hash(),
+    [valid_proof(), vec![0]].concat(),
+    valid_inputs(),
+    Err(VerifyError::InvalidProofData)
+)]
+fn report_why_verification_failed(
+    #[case] vk_hash: [u8; 32],
+    #[case] proof: Vec<u8>,
+    #[case] inputs: Vec<u8>,
+    #[case] expected: Result<(), VerifyError>,
+) {
+    // With `std`, both go through the same native code: this checks the error mapping, while
+    // `tests/wasm.rs` calls them from a wasm runtime.
+    assert_eq!(sp1_verify::verify(&vk_hash, &proof, &inputs), expected);
+    assert_eq!(verify_in_runtime(&vk_hash, &proof, &inputs), expected);
+}
+
+#[rstest]
+fn report_invalid_recursion_vk_membership(valid_vk_hash: [u8; 32], valid_inputs: Vec<u8>) {
+    let mut proof = decode_proof(&valid_proof()).unwrap();
+    proof.vk_merkle_proof.path[0] = Default::default();
+    let proof = bincode::serde::encode_to_vec(&proof, bincode::config::legacy()).unwrap();
+    assert_eq!(
+        verify_in_runtime(&valid_vk_hash, &proof, &valid_inputs),
+        Err(VerifyError::InvalidVerificationKey)
+    );
+}
+
+#[rstest]
+fn report_invalid_shrink_proof(valid_vk_hash: [u8; 32], valid_inputs: Vec<u8>) {
+    let mut proof = decode_proof(&valid_proof()).unwrap();
+    proof.shard_proof.opening_proof.fri_proof.query_proofs[0].commit_phase_openings[0]
+        .sibling_value = Default::default();
+    let proof = bincode::serde::encode_to_vec(&proof, bincode::config::legacy()).unwrap();
+    assert_eq!(
+        verify_in_runtime(&valid_vk_hash, &proof, &valid_inputs),
+        Err(VerifyError::InvalidProof)
+    );
+}
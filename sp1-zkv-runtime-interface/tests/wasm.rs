@@ -0,0 +1,117 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Call the verification from a wasm runtime, as a Substrate node does, and compare it with the
+//! native one.
+
+use codec::{Decode, Encode};
+use rstest::{fixture, rstest};
+use sc_executor::{HeapAllocStrategy, WasmExecutor};
+use sc_executor_common::runtime_blob::RuntimeBlob;
+use sp_state_machine::BasicExternalities;
+use sp1_zkv_runtime_interface::{VerifyError, sp1_verify, verify_in_runtime};
+use sp1_zkv_runtime_interface_test_wasm::WASM_BINARY;
+
+type HostFunctions = (sp_io::SubstrateHostFunctions, sp1_verify::HostFunctions);
+
+#[fixture]
+fn valid_proof() -> Vec<u8> {
+    std::fs::read("../sp1-zkv-verifier/resources/proof.bin").unwrap()
+}
+
+#[fixture]
+fn valid_vk_hash() -> [u8; 32] {
+    hex::decode("45946758049372c74bceb6ba6526a9661b1915a3403125e470d5ff7f6c15c0dd")
+        .unwrap()
+        .try_into()
+        .unwrap()
+}
+
+#[fixture]
+fn valid_inputs() -> Vec<u8> {
+    hex::decode("b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9").unwrap()
+}
+
+/// Call `method` of the test runtime, which decodes its arguments and encodes its result with
+/// SCALE.
+fn call_in_wasm(
+    method: &str,
+    vk_hash: &[u8; 32],
+    proof: &[u8],
+    inputs: &[u8],
+) -> Result<(), VerifyError> {
+    let heap = HeapAllocStrategy::Dynamic {
+        maximum_pages: None,
+    };
+    let executor = WasmExecutor::<HostFunctions>::builder()
+        .with_onchain_heap_alloc_strategy(heap)
+        .with_offchain_heap_alloc_strategy(heap)
+        .build();
+    let runtime = RuntimeBlob::uncompress_if_needed(WASM_BINARY.unwrap()).unwrap();
+    let output = executor
+        .uncached_call(
+            runtime,
+            &mut BasicExternalities::default(),
+            false,
+            method,
+            &(vk_hash, proof, inputs).encode(),
+        )
+        .unwrap();
+    Decode::decode(&mut output.as_slice()).unwrap()
+}
+
+fn flip_first_bit(mut bytes: Vec<u8>) -> Vec<u8> {
+    bytes[0] ^= 1;
+    bytes
+}
+
+#[rstest]
+#[case::valid(valid_vk_hash(), valid_proof(), valid_inputs())]
+#[case::invalid_vk_hash(
+    flip_first_bit(valid_vk_hash().to_vec()).try_into().unwrap(),
+    valid_proof(),
+    valid_inputs()
+)]
+#[case::invalid_inputs(valid_vk_hash(), valid_proof(), flip_first_bit(valid_inputs()))]
+#[case::truncated_proof(valid_vk_hash(), valid_proof()[..100].to_vec(), valid_inputs())]
+#[case::trailing_bytes(valid_vk_hash(), [valid_proof(), vec![0]].concat(), valid_inputs())]
+fn verify_from_wasm_like_natively(
+    #[case] vk_hash: [u8; 32],
+    #[case] proof: Vec<u8>,
+    #[case] inputs: Vec<u8>,
+) {
+    let native = verify_in_runtime(&vk_hash, &proof, &inputs);
+
+    assert_eq!(
+        call_in_wasm("test_verify", &vk_hash, &proof, &inputs),
+        native
+    );
+    assert_eq!(
+        call_in_wasm("test_verify_in_runtime", &vk_hash, &proof, &inputs),
+        native
+    );
+}
+
+#[rstest]
+fn accept_valid_proof_from_wasm(
+    valid_vk_hash: [u8; 32],
+    valid_proof: Vec<u8>,
+    valid_inputs: Vec<u8>,
+) {
+    assert_eq!(
+        call_in_wasm("test_verify", &valid_vk_hash, &valid_proof, &valid_inputs),
+        Ok(())
+    );
+}
@@ -0,0 +1,45 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Loaders for proving artifacts produced outside of the sp1 sdk, to be converted with
+//! [`ZkvProver::convert_reduce_proof_to_zkv`](crate::ZkvProver::convert_reduce_proof_to_zkv).
+
+use crate::{Result, SP1ZkvError};
+use sp1_core_executor::SP1ReduceProof;
+use sp1_prover::InnerSC;
+use std::{fs::File, io::BufReader, path::Path};
+
+/// Load a compressed `SP1ReduceProof` saved with bincode, as the sp1 prover does.
+pub fn load_reduce_proof(path: impl AsRef<Path>) -> Result<SP1ReduceProof<InnerSC>> {
+    let path = path.as_ref();
+    let file = File::open(path).map_err(|source| SP1ZkvError::Io {
+        path: path.to_owned(),
+        source,
+    })?;
+    bincode::serde::decode_from_std_read(&mut BufReader::new(file), bincode::config::legacy())
+        .map_err(|source| SP1ZkvError::ArtifactDecoding {
+            path: path.to_owned(),
+            source,
+        })
+}
+
+/// Load the raw public values committed by a program.
+pub fn load_public_values(path: impl AsRef<Path>) -> Result<Vec<u8>> {
+    let path = path.as_ref();
+    std::fs::read(path).map_err(|source| SP1ZkvError::Io {
+        path: path.to_owned(),
+        source,
+    })
+}
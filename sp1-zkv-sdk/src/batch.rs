@@ -0,0 +1,136 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversion of many proofs at once, see
+//! [`ZkvProver::convert_proofs_to_zkv`](crate::ZkvProver::convert_proofs_to_zkv).
+
+use sp1_stark::SP1ProverOpts;
+use std::{num::NonZeroUsize, sync::Mutex, thread};
+
+/// Default peak memory of a single conversion, used to size the concurrency of a batch against
+/// its [`ZkvBatchOpts::memory_budget`].
+///
+/// This is a conservative guess rather than a measure, as the peak depends on the prover options
+/// and the machine: measure the peak resident memory of one conversion on the target machine and
+/// set [`ZkvBatchOpts::memory_per_conversion`] to size batches precisely.
+pub const SHRINK_MEMORY_ESTIMATE: u64 = 8 << 30;
+
+/// Options of a batch conversion.
+#[derive(Debug, Clone, Default)]
+pub struct ZkvBatchOpts {
+    /// Options of every shrink.
+    pub prover_opts: SP1ProverOpts,
+    /// Memory, in bytes, the batch may use to run conversions concurrently. Without a budget
+    /// proofs are converted one at a time.
+    pub memory_budget: Option<u64>,
+    /// Peak memory, in bytes, of a single conversion, [`SHRINK_MEMORY_ESTIMATE`] by default.
+    pub memory_per_conversion: Option<u64>,
+}
+
+impl ZkvBatchOpts {
+    /// Number of conversions to run concurrently for a batch of `proofs` proofs.
+    pub fn concurrency(&self, proofs: usize) -> usize {
+        let Some(budget) = self.memory_budget else {
+            return 1;
+        };
+        let per_conversion = self
+            .memory_per_conversion
+            .unwrap_or(SHRINK_MEMORY_ESTIMATE)
+            .max(1);
+        let cores = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        ((budget / per_conversion) as usize)
+            .clamp(1, cores)
+            .min(proofs.max(1))
+    }
+}
+
+/// Apply `f` to every item on up to `workers` threads, keeping the order of `items`.
+pub(crate) fn map_concurrently<T, U, F>(items: Vec<T>, workers: usize, f: F) -> Vec<U>
+where
+    T: Send,
+    U: Send,
+    F: Fn(T) -> U + Sync,
+{
+    if workers <= 1 {
+        return items.into_iter().map(f).collect();
+    }
+
+    let len = items.len();
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let results = Mutex::new(Vec::with_capacity(len));
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                loop {
+                    // Release the queue before working on the item.
+                    let Some((index, item)) = queue.lock().unwrap().next() else {
+                        break;
+                    };
+                    let result = f(item);
+                    results.lock().unwrap().push((index, result));
+                }
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_unstable_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_concurrently_keeps_order() {
+        let items = (0..100).collect::<Vec<u64>>();
+        assert_eq!(
+            map_concurrently(items.clone(), 4, |i| i * i),
+            items.iter().map(|i| i * i).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn concurrency_follows_memory_budget() {
+        let opts = |memory_budget| ZkvBatchOpts {
+            memory_budget,
+            ..Default::default()
+        };
+        assert_eq!(opts(None).concurrency(10), 1);
+        assert_eq!(opts(Some(SHRINK_MEMORY_ESTIMATE - 1)).concurrency(10), 1);
+        assert!(opts(Some(4 * SHRINK_MEMORY_ESTIMATE)).concurrency(10) <= 4);
+        assert_eq!(opts(Some(4 * SHRINK_MEMORY_ESTIMATE)).concurrency(1), 1);
+    }
+
+    #[test]
+    fn concurrency_follows_memory_per_conversion() {
+        let opts = |memory_per_conversion| ZkvBatchOpts {
+            memory_budget: Some(SHRINK_MEMORY_ESTIMATE),
+            memory_per_conversion,
+            ..Default::default()
+        };
+        assert_eq!(opts(Some(SHRINK_MEMORY_ESTIMATE + 1)).concurrency(10), 1);
+        assert_eq!(
+            opts(Some(SHRINK_MEMORY_ESTIMATE / 2)).concurrency(10),
+            2.min(cores())
+        );
+        assert_eq!(opts(Some(0)).concurrency(10), 10.min(cores()));
+    }
+
+    fn cores() -> usize {
+        thread::available_parallelism().map_or(1, NonZeroUsize::get)
+    }
+}
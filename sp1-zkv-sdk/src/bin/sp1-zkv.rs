@@ -0,0 +1,429 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Command-line access to the zkVerify proof pipeline: prove, convert, verify and inspect SP1
+//! proofs, and compute program vk digests.
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::{Serialize, de::DeserializeOwned};
+use sp1_sdk::{HashableKey, Prover, ProverClient, SP1ProofWithPublicValues, SP1Stdin};
+use sp1_zkv_sdk::{
+    NUM_PUBLIC_VALUES, ProofMetadata, SP1ZkvProofWithPublicValues, ZkvProveBuilder, ZkvProver,
+};
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+type CliResult<T = ()> = Result<T, Box<dyn Error>>;
+
+#[derive(Parser)]
+#[command(name = "sp1-zkv", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Prove the execution of a program directly as a zkv proof.
+    Prove {
+        /// The program ELF.
+        #[arg(long)]
+        elf: PathBuf,
+        /// The program input, a serialized `SP1Stdin`.
+        #[arg(long)]
+        stdin: PathBuf,
+        #[arg(long, value_enum, default_value_t)]
+        stdin_format: Format,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// Convert a compressed `SP1ProofWithPublicValues` into a zkv proof.
+    Convert {
+        #[command(flatten)]
+        proof: ProofInput,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// Verify a zkv proof.
+    Verify {
+        #[command(flatten)]
+        proof: ProofInput,
+        /// The program vk digest, in hex.
+        #[arg(long)]
+        vk_hash: String,
+        /// The public inputs in hex, if not the ones in the proof.
+        #[arg(long)]
+        inputs: Option<String>,
+    },
+    /// Print the public values, vk digest, chip shapes and sizes of a zkv proof.
+    Inspect {
+        #[command(flatten)]
+        proof: ProofInput,
+        /// Print as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the vk digest of a program in all its formats.
+    VkHash {
+        /// The program ELF.
+        #[arg(long)]
+        elf: PathBuf,
+        /// Print as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Args)]
+struct ProofInput {
+    /// The proof file. zkv proofs may also be envelopes saved by the sdk, in binary or JSON.
+    #[arg(long)]
+    proof: PathBuf,
+    #[arg(long, value_enum, default_value_t)]
+    proof_format: Format,
+}
+
+#[derive(Args)]
+struct Output {
+    /// Where to write the zkv proof, standard output if missing.
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+    #[arg(long, value_enum, default_value_t)]
+    format: Format,
+}
+
+/// Encoding of a file: bincode as the sp1 sdk saves it, that bincode encoding in hex, or JSON.
+#[derive(Clone, Copy, Default, ValueEnum)]
+enum Format {
+    #[default]
+    Bin,
+    Hex,
+    Json,
+}
+
+impl Format {
+    fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> CliResult<T> {
+        Ok(match self {
+            Format::Bin => decode_bincode(bytes)?,
+            Format::Hex => decode_bincode(&decode_hex(std::str::from_utf8(bytes)?)?)?,
+            Format::Json => serde_json::from_slice(bytes)?,
+        })
+    }
+
+    fn encode<T: Serialize>(self, value: &T) -> CliResult<Vec<u8>> {
+        Ok(match self {
+            Format::Bin => bincode::serde::encode_to_vec(value, bincode::config::legacy())?,
+            Format::Hex => hex::encode(bincode::serde::encode_to_vec(
+                value,
+                bincode::config::legacy(),
+            )?)
+            .into_bytes(),
+            Format::Json => serde_json::to_vec_pretty(value)?,
+        })
+    }
+}
+
+fn decode_bincode<T: DeserializeOwned>(bytes: &[u8]) -> CliResult<T> {
+    let (value, read) = bincode::serde::decode_from_slice(bytes, bincode::config::legacy())?;
+    if read != bytes.len() {
+        return Err(format!(
+            "{} unexpected bytes after the encoded value",
+            bytes.len() - read
+        )
+        .into());
+    }
+    Ok(value)
+}
+
+fn decode_hex(hex: &str) -> CliResult<Vec<u8>> {
+    let hex = hex.trim();
+    Ok(hex::decode(hex.strip_prefix("0x").unwrap_or(hex))?)
+}
+
+fn read<T: DeserializeOwned>(path: &Path, format: Format) -> CliResult<T> {
+    format.decode(&std::fs::read(path)?)
+}
+
+/// Read a zkv proof, either encoded as is or in the envelope written by
+/// [`SP1ZkvProofWithPublicValues::save`] or [`SP1ZkvProofWithPublicValues::to_json`].
+fn read_zkv_proof(input: &ProofInput) -> CliResult<SP1ZkvProofWithPublicValues> {
+    match input.proof_format {
+        // Loading tells envelopes apart from bare proofs by their magic bytes.
+        Format::Bin => Ok(SP1ZkvProofWithPublicValues::load(&input.proof)?),
+        Format::Hex => read(&input.proof, Format::Hex),
+        Format::Json => {
+            let json = std::fs::read_to_string(&input.proof)?;
+            let value: serde_json::Value = serde_json::from_str(&json)?;
+            if value.get("checksum").is_some() {
+                Ok(SP1ZkvProofWithPublicValues::from_json(&json)?.0)
+            } else {
+                Ok(serde_json::from_value(value)?)
+            }
+        }
+    }
+}
+
+fn write<T: Serialize>(value: &T, output: &Output) -> CliResult {
+    let bytes = output.format.encode(value)?;
+    match &output.output {
+        Some(path) => std::fs::write(path, bytes)?,
+        None => std::io::stdout().write_all(&bytes)?,
+    }
+    Ok(())
+}
+
+/// Shape of a chip in a shard proof.
+#[derive(Serialize)]
+struct ChipShape {
+    name: String,
+    log_degree: usize,
+    preprocessed_width: usize,
+    main_width: usize,
+    permutation_width: usize,
+    quotient_chunks: usize,
+}
+
+/// What `inspect` reports about a zkv proof.
+#[derive(Serialize)]
+struct Inspection {
+    vk_hash: String,
+    public_values: String,
+    proof_size: usize,
+    public_values_size: usize,
+    chips: Vec<ChipShape>,
+}
+
+fn inspect(proof: &SP1ZkvProofWithPublicValues) -> CliResult<Inspection> {
+    // The claimed vk digest is read out of the recursion public values, which an untrusted proof
+    // may not have.
+    let num_public_values = proof.proof.shard_proof.public_values.len();
+    if num_public_values != NUM_PUBLIC_VALUES {
+        let expected = NUM_PUBLIC_VALUES;
+        return Err(format!(
+            "the shard proof has {num_public_values} public values, not {expected}"
+        )
+        .into());
+    }
+    let vk_hash =
+        sp1_zkv_verifier::claimed_vkey_digest(&sp1_zkv_verifier::Proof::try_from(&proof.proof)?);
+    let shard_proof = &proof.proof.shard_proof;
+    let names = shard_proof
+        .chip_ordering
+        .iter()
+        .map(|(name, index)| (*index, name.clone()))
+        .collect::<BTreeMap<_, _>>();
+    let chips = shard_proof
+        .opened_values
+        .chips
+        .iter()
+        .enumerate()
+        .map(|(index, chip)| ChipShape {
+            name: names.get(&index).cloned().unwrap_or_default(),
+            log_degree: chip.log_degree,
+            preprocessed_width: chip.preprocessed.local.len(),
+            main_width: chip.main.local.len(),
+            permutation_width: chip.permutation.local.len(),
+            quotient_chunks: chip.quotient.len(),
+        })
+        .collect();
+    Ok(Inspection {
+        vk_hash: format!("0x{}", hex::encode(vk_hash)),
+        public_values: format!("0x{}", hex::encode(&proof.public_values)),
+        proof_size: bincode::serde::encode_to_vec(&proof.proof, bincode::config::legacy())?.len(),
+        public_values_size: proof.public_values.len(),
+        chips,
+    })
+}
+
+fn main() -> CliResult {
+    match Cli::parse().command {
+        Command::Prove {
+            elf,
+            stdin,
+            stdin_format,
+            output,
+        } => {
+            let stdin: SP1Stdin = read(&stdin, stdin_format)?;
+            let prover = ProverClient::builder().cpu().build();
+            let (pk, _) = prover.setup(&std::fs::read(elf)?);
+            let (zkv_proof, vk_hash) = prover.prove(&pk, &stdin).compressed().zkv(&prover).run()?;
+            eprintln!("vk hash: 0x{}", hex::encode(vk_hash));
+            write(&zkv_proof, &output)?;
+        }
+        Command::Convert { proof, output } => {
+            let proof: SP1ProofWithPublicValues = read(&proof.proof, proof.proof_format)?;
+            let prover = ProverClient::builder().cpu().build();
+            write(&prover.convert_proof_to_zkv(proof, None)?, &output)?;
+        }
+        Command::Verify {
+            proof,
+            vk_hash,
+            inputs,
+        } => {
+            let mut proof = read_zkv_proof(&proof)?;
+            if let Some(inputs) = inputs {
+                proof.public_values = decode_hex(&inputs)?;
+            }
+            let vk_hash: [u8; 32] = decode_hex(&vk_hash)?
+                .try_into()
+                .map_err(|_| "vk hash must be 32 bytes")?;
+            sp1_zkv_sdk::verify(&proof, &vk_hash)?;
+            println!("Proof verified.");
+        }
+        Command::Inspect { proof, json } => {
+            let inspection = inspect(&read_zkv_proof(&proof)?)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&inspection)?);
+            } else {
+                println!("vk hash: {}", inspection.vk_hash);
+                println!("public values: {}", inspection.public_values);
+                println!("proof size: {} bytes", inspection.proof_size);
+                println!(
+                    "public values size: {} bytes",
+                    inspection.public_values_size
+                );
+                println!("chips:");
+                for chip in &inspection.chips {
+                    println!(
+                        "  {}: log degree {}, widths preprocessed {} main {} permutation {}, \
+                         {} quotient chunks",
+                        chip.name,
+                        chip.log_degree,
+                        chip.preprocessed_width,
+                        chip.main_width,
+                        chip.permutation_width,
+                        chip.quotient_chunks
+                    );
+                }
+            }
+        }
+        Command::VkHash { elf, json } => {
+            let prover = ProverClient::builder().cpu().build();
+            let (_, vk) = prover.setup(&std::fs::read(elf)?);
+            let digests = BTreeMap::from([
+                ("bytes32", vk.bytes32()),
+                ("hex", format!("0x{}", hex::encode(vk.hash_bytes()))),
+                ("u32", format!("{:?}", vk.hash_u32())),
+            ]);
+            if json {
+                println!("{}", serde_json::to_string_pretty(&digests)?);
+            } else {
+                for (format, digest) in digests {
+                    println!("{format}: {digest}");
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valid_proof() -> SP1ZkvProofWithPublicValues {
+        read(
+            Path::new("../sp1-zkv-verifier/resources/proof.bin"),
+            Format::Bin,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn decode_hex_with_or_without_prefix() {
+        assert_eq!(decode_hex("0x0a0B").unwrap(), [0x0a, 0x0b]);
+        assert_eq!(decode_hex(" 0a0b\n").unwrap(), [0x0a, 0x0b]);
+        assert!(decode_hex("0x0g").is_err());
+    }
+
+    #[test]
+    fn round_trip_through_every_format() {
+        let value = (42u32, "sp1".to_string(), vec![1u8, 2, 3]);
+        for format in [Format::Bin, Format::Hex, Format::Json] {
+            let decoded: (u32, String, Vec<u8>) =
+                format.decode(&format.encode(&value).unwrap()).unwrap();
+            assert_eq!(decoded, value);
+        }
+    }
+
+    #[test]
+    fn reject_trailing_bytes() {
+        let mut bytes = Format::Bin.encode(&42u32).unwrap();
+        bytes.push(0);
+        assert!(Format::Bin.decode::<u32>(&bytes).is_err());
+
+        let hex = Format::Hex.encode(&bytes).unwrap();
+        let mut hex_with_trailing_bytes = hex.clone();
+        hex_with_trailing_bytes.extend_from_slice(b"00");
+        assert!(Format::Hex.decode::<Vec<u8>>(&hex).is_ok());
+        assert!(
+            Format::Hex
+                .decode::<Vec<u8>>(&hex_with_trailing_bytes)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn inspect_valid_proof() {
+        let inspection = inspect(&valid_proof()).unwrap();
+
+        assert_eq!(
+            inspection.vk_hash,
+            "0x45946758049372c74bceb6ba6526a9661b1915a3403125e470d5ff7f6c15c0dd"
+        );
+        assert_eq!(
+            inspection.public_values,
+            "0xb94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+        );
+        assert!(!inspection.chips.is_empty());
+    }
+
+    #[test]
+    fn read_proof_envelopes() {
+        let proof = valid_proof();
+        let metadata = ProofMetadata::new(&proof).unwrap();
+        let bin = std::env::temp_dir().join(format!("sp1-zkv-cli-{}.bin", std::process::id()));
+        let json = bin.with_extension("json");
+        proof.save_with_metadata(&bin, &metadata).unwrap();
+        std::fs::write(&json, proof.to_json(&metadata).unwrap()).unwrap();
+
+        let read_bin = read_zkv_proof(&ProofInput {
+            proof: bin.clone(),
+            proof_format: Format::Bin,
+        });
+        let read_json = read_zkv_proof(&ProofInput {
+            proof: json.clone(),
+            proof_format: Format::Json,
+        });
+        std::fs::remove_file(&bin).unwrap();
+        std::fs::remove_file(&json).unwrap();
+
+        let encoded = Format::Bin.encode(&proof).unwrap();
+        assert_eq!(Format::Bin.encode(&read_bin.unwrap()).unwrap(), encoded);
+        assert_eq!(Format::Bin.encode(&read_json.unwrap()).unwrap(), encoded);
+    }
+
+    #[test]
+    fn not_inspect_proof_without_recursion_public_values() {
+        let mut proof = valid_proof();
+        proof.proof.shard_proof.public_values.truncate(1);
+
+        assert!(inspect(&proof).is_err());
+    }
+}
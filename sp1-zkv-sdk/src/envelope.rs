@@ -0,0 +1,281 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Self-describing persistence of [`SP1ZkvProofWithPublicValues`].
+//!
+//! Proofs are saved in an envelope holding their [`ProofMetadata`] and a checksum, either as a
+//! binary file with [`SP1ZkvProofWithPublicValues::save`] or as JSON with
+//! [`SP1ZkvProofWithPublicValues::to_json`]. Both carry the same fields:
+//!
+//! - `version`: the envelope format version, [`FORMAT_VERSION`];
+//! - `metadata`: SP1 version, program vk digest, creation time and program name;
+//! - `checksum`: the Keccak-256 of the legacy bincode encoding of all the other fields, in this
+//!   order: `version`, `metadata`, `proof` and `public_values`;
+//! - `proof`: the legacy bincode encoding of the [`Proof`], as the zkVerify pallet decodes it;
+//! - `public_values`: the public values.
+//!
+//! A binary file is the [`MAGIC`] bytes followed by the legacy bincode encoding of these fields,
+//! and nothing else, so its version is always found in bytes 6 and 7. Byte fields, vk digest and
+//! checksum included, are prefixed by their length as a little endian `u64`. In JSON, they are
+//! `0x` prefixed hex strings.
+//!
+//! Since the proof is kept as opaque bytes, the envelope does not depend on the proof layout, and
+//! newer format versions are reported as such before anything else is decoded.
+
+use crate::{Proof, Result, SP1ZkvError, SP1ZkvProofWithPublicValues};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use sha3::{Digest, Keccak256};
+use std::{
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Leading bytes of a saved proof file.
+pub const MAGIC: [u8; 6] = *b"SP1ZKV";
+
+/// Version of the envelope format written by this sdk.
+pub const FORMAT_VERSION: u16 = 1;
+
+/// Metadata saved along with a proof.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofMetadata {
+    /// The SP1 version the proof was produced with.
+    pub sp1_version: String,
+    /// The program vk digest.
+    #[serde(with = "hex_bytes")]
+    pub vkey_digest: [u8; 32],
+    /// Creation time, in seconds since the Unix epoch.
+    pub created_at: u64,
+    /// The name of the proven program, if known.
+    pub program_name: Option<String>,
+}
+
+impl ProofMetadata {
+    /// Metadata of `proof`, created now by the SP1 version of this sdk. The vk digest is the one
+    /// claimed by the proof.
+    pub fn new(proof: &SP1ZkvProofWithPublicValues) -> Result<Self> {
+        let vkey_digest = sp1_zkv_verifier::claimed_vkey_digest(
+            &sp1_zkv_verifier::Proof::try_from(&proof.proof)?,
+        );
+        Ok(Self {
+            sp1_version: sp1_sdk::SP1_CIRCUIT_VERSION.to_string(),
+            vkey_digest,
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or_default(),
+            program_name: None,
+        })
+    }
+
+    /// Set the name of the proven program.
+    pub fn with_program_name(mut self, program_name: impl Into<String>) -> Self {
+        self.program_name = Some(program_name.into());
+        self
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    version: u16,
+    metadata: ProofMetadata,
+    #[serde(with = "hex_bytes")]
+    checksum: [u8; 32],
+    #[serde(with = "hex_bytes")]
+    proof: Vec<u8>,
+    #[serde(with = "hex_bytes")]
+    public_values: Vec<u8>,
+}
+
+/// The fields of an [`Envelope`] its checksum covers, in the same order.
+#[derive(Serialize)]
+struct Checked<'a> {
+    version: u16,
+    metadata: &'a ProofMetadata,
+    proof: &'a [u8],
+    public_values: &'a [u8],
+}
+
+impl Checked<'_> {
+    fn checksum(&self) -> Result<[u8; 32]> {
+        let encoded = bincode::serde::encode_to_vec(self, bincode::config::legacy())?;
+        Ok(Keccak256::digest(encoded).into())
+    }
+}
+
+impl Envelope {
+    fn new(proof: &SP1ZkvProofWithPublicValues, metadata: &ProofMetadata) -> Result<Self> {
+        let encoded = bincode::serde::encode_to_vec(&proof.proof, bincode::config::legacy())?;
+        let checksum = Checked {
+            version: FORMAT_VERSION,
+            metadata,
+            proof: &encoded,
+            public_values: &proof.public_values,
+        }
+        .checksum()?;
+        Ok(Self {
+            version: FORMAT_VERSION,
+            metadata: metadata.clone(),
+            checksum,
+            proof: encoded,
+            public_values: proof.public_values.clone(),
+        })
+    }
+
+    fn open(self) -> Result<(SP1ZkvProofWithPublicValues, ProofMetadata)> {
+        if self.version != FORMAT_VERSION {
+            return Err(SP1ZkvError::UnsupportedFormatVersion(self.version));
+        }
+        let checked = Checked {
+            version: self.version,
+            metadata: &self.metadata,
+            proof: &self.proof,
+            public_values: &self.public_values,
+        };
+        if checked.checksum()? != self.checksum {
+            return Err(SP1ZkvError::ChecksumMismatch);
+        }
+        let (proof, read): (Proof, _) =
+            bincode::serde::decode_from_slice(&self.proof, bincode::config::legacy())?;
+        if read != self.proof.len() {
+            return Err(SP1ZkvError::ProofTrailingBytes(self.proof.len() - read));
+        }
+        let proof = SP1ZkvProofWithPublicValues {
+            proof,
+            public_values: self.public_values,
+        };
+        Ok((proof, self.metadata))
+    }
+}
+
+impl SP1ZkvProofWithPublicValues {
+    /// Save the proof to `path` with default [`ProofMetadata`].
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        self.save_with_metadata(path, &ProofMetadata::new(self)?)
+    }
+
+    /// Save the proof to `path` with `metadata`.
+    pub fn save_with_metadata(
+        &self,
+        path: impl AsRef<Path>,
+        metadata: &ProofMetadata,
+    ) -> Result<()> {
+        let path = path.as_ref();
+        let mut bytes = MAGIC.to_vec();
+        bincode::serde::encode_into_std_write(
+            Envelope::new(self, metadata)?,
+            &mut bytes,
+            bincode::config::legacy(),
+        )?;
+        std::fs::write(path, bytes).map_err(|source| SP1ZkvError::Io {
+            path: path.to_owned(),
+            source,
+        })
+    }
+
+    /// Load a proof saved with [`save`](Self::save). Files holding just the legacy bincode
+    /// encoding of the proof, as written by earlier versions of this sdk, are accepted too.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = read(path)?;
+        if !bytes.starts_with(&MAGIC) {
+            return decode_file(path, &bytes);
+        }
+        open_file(path, &bytes).map(|(proof, _)| proof)
+    }
+
+    /// Load a proof saved with [`save`](Self::save), along with its metadata.
+    pub fn load_with_metadata(path: impl AsRef<Path>) -> Result<(Self, ProofMetadata)> {
+        let path = path.as_ref();
+        let bytes = read(path)?;
+        if !bytes.starts_with(&MAGIC) {
+            return Err(SP1ZkvError::MissingMagic(path.to_owned()));
+        }
+        open_file(path, &bytes)
+    }
+
+    /// The JSON representation of the proof with `metadata`.
+    pub fn to_json(&self, metadata: &ProofMetadata) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&Envelope::new(
+            self, metadata,
+        )?)?)
+    }
+
+    /// Decode the JSON representation of a proof, along with its metadata.
+    pub fn from_json(json: &str) -> Result<(Self, ProofMetadata)> {
+        serde_json::from_str::<Envelope>(json)?.open()
+    }
+}
+
+fn read(path: &Path) -> Result<Vec<u8>> {
+    std::fs::read(path).map_err(|source| SP1ZkvError::Io {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+fn open_file(path: &Path, bytes: &[u8]) -> Result<(SP1ZkvProofWithPublicValues, ProofMetadata)> {
+    let version = bytes
+        .get(MAGIC.len()..MAGIC.len() + 2)
+        .map(|version| u16::from_le_bytes([version[0], version[1]]));
+    if let Some(version) = version.filter(|version| *version != FORMAT_VERSION) {
+        return Err(SP1ZkvError::UnsupportedFormatVersion(version));
+    }
+    decode_file::<Envelope>(path, &bytes[MAGIC.len()..])?.open()
+}
+
+/// Decode the whole of `bytes`, read from `path`.
+fn decode_file<T: DeserializeOwned>(path: &Path, bytes: &[u8]) -> Result<T> {
+    let (value, read) = bincode::serde::decode_from_slice(bytes, bincode::config::legacy())
+        .map_err(|source| SP1ZkvError::ArtifactDecoding {
+            path: path.to_owned(),
+            source,
+        })?;
+    if read != bytes.len() {
+        return Err(SP1ZkvError::ProofTrailingBytes(bytes.len() - read));
+    }
+    Ok(value)
+}
+
+/// Byte fields as `0x` prefixed hex in human readable formats, and as plain bytes otherwise.
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer>(
+        bytes: impl AsRef<[u8]>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+        } else {
+            serde::Serialize::serialize(bytes.as_ref(), serializer)
+        }
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: TryFrom<Vec<u8>>,
+    {
+        let bytes = if deserializer.is_human_readable() {
+            let hex = String::deserialize(deserializer)?;
+            hex::decode(hex.strip_prefix("0x").unwrap_or(&hex)).map_err(D::Error::custom)?
+        } else {
+            Vec::<u8>::deserialize(deserializer)?
+        };
+        let len = bytes.len();
+        T::try_from(bytes).map_err(|_| D::Error::invalid_length(len, &"a 32 bytes digest"))
+    }
+}
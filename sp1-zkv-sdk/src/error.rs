@@ -0,0 +1,108 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sp1_prover::SP1RecursionProverError;
+use sp1_sdk::SP1ProofMode;
+use std::{path::PathBuf, time::Duration};
+use thiserror::Error;
+
+pub type Result<T, E = SP1ZkvError> = std::result::Result<T, E>;
+
+/// Error returned by the sdk.
+#[derive(Error, Debug)]
+pub enum SP1ZkvError {
+    #[error("Unsupported proof format: Groth16 and Plonk proofs cannot be shrunk.")]
+    UnsupportedProofFormat,
+    #[error("Core proofs lack the vk needed to compress them: use `convert_core_proof_to_zkv`.")]
+    MissingVerifyingKey,
+    #[error("Only `Compressed` prove requests can be shrunk, not {0:?} ones.")]
+    ProofMode(SP1ProofMode),
+    #[error("Proving failed: {0}")]
+    Prove(#[source] anyhow::Error),
+    #[error("Compress failed: {0}")]
+    Compress(#[source] SP1RecursionProverError),
+    #[error("Shrink failed: {0}")]
+    Shrink(#[source] SP1RecursionProverError),
+    #[error("Shrink panicked: {0}")]
+    ShrinkPanicked(String),
+    #[error("Cannot build the recursion vk Merkle proof: {0}")]
+    MerkleProof(#[from] MerkleProofError),
+    #[error("Cannot encode proof: {0}")]
+    ProofEncoding(#[from] bincode::error::EncodeError),
+    #[error("Cannot decode proof: {0}")]
+    ProofDecoding(#[from] bincode::error::DecodeError),
+    #[error("Proof is followed by {0} unexpected bytes.")]
+    ProofTrailingBytes(usize),
+    #[error("Cannot read {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Cannot decode {}: {source}", path.display())]
+    ArtifactDecoding {
+        path: PathBuf,
+        #[source]
+        source: bincode::error::DecodeError,
+    },
+    #[error("{} is not a saved zkv proof.", .0.display())]
+    MissingMagic(PathBuf),
+    #[error("Unsupported proof file format version {0}.")]
+    UnsupportedFormatVersion(u16),
+    #[error("Proof checksum mismatch.")]
+    ChecksumMismatch,
+    #[error("Invalid proof JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Proof does not verify: {0}")]
+    Verification(#[source] sp1_zkv_verifier::Error),
+    #[error("Converted proof does not verify: {0}")]
+    SelfVerification(#[source] sp1_zkv_verifier::Error),
+    #[error("Conversion cancelled.")]
+    Cancelled,
+    #[error("Conversion timed out after {0:?}.")]
+    TimedOut(Duration),
+}
+
+/// Error building recursion vk Merkle proofs.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MerkleProofError {
+    /// The prover panicked, e.g. on a vk missing from its vk map.
+    #[error("prover panicked: {0}")]
+    Panicked(String),
+    /// The prover returned fewer Merkle proofs than it was given proofs.
+    #[error("no Merkle proof returned")]
+    Missing,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error as _;
+
+    #[test]
+    fn wrapped_errors_are_sources() {
+        let error = SP1ZkvError::from(MerkleProofError::Missing);
+        assert_eq!(
+            error.source().map(ToString::to_string),
+            Some(MerkleProofError::Missing.to_string())
+        );
+
+        let error = SP1ZkvError::Prove(anyhow::anyhow!("out of cycles"));
+        assert_eq!(
+            error.source().map(ToString::to_string),
+            Some("out of cycles".to_string())
+        );
+    }
+}
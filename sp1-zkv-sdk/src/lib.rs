@@ -13,15 +13,37 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use anyhow::Result;
+pub mod artifacts;
+mod batch;
+pub mod envelope;
+mod error;
+pub mod progress;
+mod request;
+pub mod simulator;
+pub mod submission;
+
+use crate::progress::{Phase, Tracker};
 use p3_baby_bear::BabyBear;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use sp1_core_executor::SP1ReduceProof;
-use sp1_prover::{InnerSC, components::SP1ProverComponents};
+use sp1_prover::{
+    InnerSC, SP1CoreProof, SP1CoreProofData, SP1Prover, components::SP1ProverComponents,
+};
 use sp1_recursion_circuit::{machine::SP1CompressWitnessValues, merkle_tree::MerkleProof};
-use sp1_sdk::{Prover, SP1ProofWithPublicValues};
+use sp1_sdk::{Prover, SP1Proof, SP1ProofWithPublicValues, SP1Stdin, SP1VerifyingKey};
 use sp1_stark::{SP1ProverOpts, StarkVerifyingKey, baby_bear_poseidon2::BabyBearPoseidon2};
-use thiserror::Error;
+use std::{any::Any, panic::AssertUnwindSafe};
+
+pub use batch::{SHRINK_MEMORY_ESTIMATE, ZkvBatchOpts};
+pub use envelope::ProofMetadata;
+pub use error::{MerkleProofError, Result, SP1ZkvError};
+pub use progress::ZkvConvertOpts;
+pub use request::{ZkvProveBuilder, ZkvProveRequest};
+pub use sp1_zkv_verifier::{
+    NUM_PUBLIC_VALUES, SP1_CONTEXT, aggregation_root, is_statement_included, statement_hash,
+    statement_leaf,
+};
+pub use submission::{Submission, VkOrHash};
 
 type ShardProof = sp1_stark::ShardProof<BabyBearPoseidon2>;
 
@@ -38,12 +60,69 @@ pub struct SP1ZkvProofWithPublicValues {
     pub public_values: Vec<u8>,
 }
 
+impl SP1ZkvProofWithPublicValues {
+    /// The zkVerify statement hash of this proof for program `vkey`, see [`statement_hash`].
+    pub fn statement_hash(&self, vkey: &[u8; 32]) -> [u8; 32] {
+        statement_hash(vkey, &self.public_values)
+    }
+
+    /// The leaf of this proof in a zkVerify aggregation tree, see [`statement_leaf`].
+    pub fn statement_leaf(&self, vkey: &[u8; 32]) -> [u8; 32] {
+        statement_leaf(vkey, &self.public_values)
+    }
+}
+
 pub trait ZkvProver<C> {
     fn convert_proof_to_zkv(
         &self,
         proof: SP1ProofWithPublicValues,
         opts: Option<SP1ProverOpts>,
     ) -> Result<SP1ZkvProofWithPublicValues>;
+
+    /// Convert a `Core` or `Compressed` proof of the program with verifying key `vk`, compressing
+    /// it first if needed. `stdin` is the input the program was proven on, which also holds the
+    /// proofs it verified, if any; `opts` apply to both compress and shrink.
+    ///
+    /// Unlike compressed proofs, `Core` proofs don't carry the program verifying key, which
+    /// compressing them needs, so [`convert_proof_to_zkv`](ZkvProver::convert_proof_to_zkv)
+    /// rejects them with [`SP1ZkvError::MissingVerifyingKey`].
+    fn convert_core_proof_to_zkv(
+        &self,
+        proof: SP1ProofWithPublicValues,
+        vk: &SP1VerifyingKey,
+        stdin: &SP1Stdin,
+        opts: Option<SP1ProverOpts>,
+    ) -> Result<SP1ZkvProofWithPublicValues>;
+
+    /// Convert a compressed proof produced outside of the sp1 sdk, committing to
+    /// `public_values`. See [`artifacts`] to load one from disk.
+    fn convert_reduce_proof_to_zkv(
+        &self,
+        reduce_proof: SP1ReduceProof<InnerSC>,
+        public_values: Vec<u8>,
+        opts: Option<SP1ProverOpts>,
+    ) -> Result<SP1ZkvProofWithPublicValues>;
+
+    /// Convert a compressed proof like [`convert_proof_to_zkv`](ZkvProver::convert_proof_to_zkv),
+    /// reporting the progress of each phase and stopping on cancellation or timeout as set in
+    /// `opts`.
+    ///
+    /// Phases can't be interrupted: cancellation and timeout are checked between them, so the
+    /// conversion stops once the phase it was cancelled or timed out in has finished.
+    fn convert_proof_to_zkv_with(
+        &self,
+        proof: SP1ProofWithPublicValues,
+        opts: &ZkvConvertOpts,
+    ) -> Result<SP1ZkvProofWithPublicValues>;
+
+    /// Convert many compressed proofs, possibly concurrently, sharing the prover setup and
+    /// computing the recursion vk Merkle proofs of the whole batch at once, or one proof at a time
+    /// if that fails. Results are in the order of `proofs`.
+    fn convert_proofs_to_zkv(
+        &self,
+        proofs: Vec<SP1ProofWithPublicValues>,
+        opts: Option<ZkvBatchOpts>,
+    ) -> Vec<Result<SP1ZkvProofWithPublicValues>>;
 }
 
 impl<T, C> ZkvProver<C> for T
@@ -56,45 +135,273 @@ where
         proof: SP1ProofWithPublicValues,
         opts: Option<SP1ProverOpts>,
     ) -> Result<SP1ZkvProofWithPublicValues> {
-        let compressed_proof = proof
-            .proof
-            .try_as_compressed()
-            .ok_or(SP1ZkvError::UnsupportedProofFormat)?;
+        shrink_to_zkv(
+            self.inner(),
+            into_compressed(proof.proof)?,
+            proof.public_values.to_vec(),
+            opts.unwrap_or_default(),
+        )
+    }
+
+    fn convert_proof_to_zkv_with(
+        &self,
+        proof: SP1ProofWithPublicValues,
+        opts: &ZkvConvertOpts,
+    ) -> Result<SP1ZkvProofWithPublicValues> {
+        let tracker = Tracker::new(opts);
+        let compressed_proof = into_compressed(proof.proof)?;
         let SP1ReduceProof {
             vk,
             proof: shard_proof,
-        } = self
-            .inner()
-            .shrink(*compressed_proof, opts.unwrap_or_default())?;
-        let input = SP1CompressWitnessValues {
-            vks_and_proofs: vec![(vk.clone(), shard_proof.clone())],
-            is_complete: true,
-        };
-        let proof_with_vk_and_merkle = self.inner().make_merkle_proofs(input);
-        Ok(SP1ZkvProofWithPublicValues {
+        } = tracker.run(Phase::Shrink, || {
+            shrink(self.inner(), compressed_proof, opts.prover_opts.clone())
+        })?;
+        let vk_merkle_proof = tracker.run(Phase::MerkleProof, || {
+            make_vk_merkle_proof(self.inner(), vk.clone(), shard_proof.clone())
+        })?;
+        let zkv_proof = SP1ZkvProofWithPublicValues {
             proof: Proof {
                 shard_proof,
                 vk,
-                vk_merkle_proof: proof_with_vk_and_merkle.merkle_val.vk_merkle_proofs[0].clone(),
+                vk_merkle_proof,
             },
             public_values: proof.public_values.to_vec(),
-        })
+        };
+        if opts.self_verify {
+            tracker.run(Phase::SelfVerify, || {
+                let proof = sp1_zkv_verifier::Proof::try_from(&zkv_proof.proof)?;
+                let vkey = sp1_zkv_verifier::claimed_vkey_digest(&proof);
+                sp1_zkv_verifier::verify(&vkey, &proof, &zkv_proof.public_values)
+                    .map_err(SP1ZkvError::SelfVerification)
+            })?;
+        }
+        tracker.check()?;
+        Ok(zkv_proof)
+    }
+
+    fn convert_proofs_to_zkv(
+        &self,
+        proofs: Vec<SP1ProofWithPublicValues>,
+        opts: Option<ZkvBatchOpts>,
+    ) -> Vec<Result<SP1ZkvProofWithPublicValues>> {
+        let opts = opts.unwrap_or_default();
+        let workers = opts.concurrency(proofs.len());
+        let shrunk = batch::map_concurrently(proofs, workers, |proof| -> Result<_> {
+            let compressed_proof = into_compressed(proof.proof)?;
+            // A panicking shrink must only fail its own proof, not the whole batch.
+            let shrunk = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                shrink(self.inner(), compressed_proof, opts.prover_opts.clone())
+            }))
+            .map_err(|panic| SP1ZkvError::ShrinkPanicked(panic_message(panic)))??;
+            Ok((shrunk, proof.public_values.to_vec()))
+        });
+
+        let vks_and_proofs = shrunk
+            .iter()
+            .flatten()
+            .map(|(shrunk, _)| (shrunk.vk.clone(), shrunk.proof.clone()))
+            .collect::<Vec<_>>();
+        let mut vk_merkle_proofs = match make_vk_merkle_proofs(self.inner(), vks_and_proofs) {
+            Ok(vk_merkle_proofs) => vk_merkle_proofs.into_iter().map(Ok).collect(),
+            // A single vk missing from the vk map fails the whole batch, so build the proofs one
+            // at a time to fail only the proofs at fault.
+            Err(_) => shrunk
+                .iter()
+                .flatten()
+                .map(|(shrunk, _)| {
+                    make_vk_merkle_proof(self.inner(), shrunk.vk.clone(), shrunk.proof.clone())
+                })
+                .collect::<Vec<_>>(),
+        }
+        .into_iter();
+
+        shrunk
+            .into_iter()
+            .map(|result| {
+                let (SP1ReduceProof { vk, proof }, public_values) = result?;
+                let vk_merkle_proof = vk_merkle_proofs
+                    .next()
+                    .unwrap_or_else(|| Err(SP1ZkvError::MerkleProof(MerkleProofError::Missing)))?;
+                Ok(SP1ZkvProofWithPublicValues {
+                    proof: Proof {
+                        shard_proof: proof,
+                        vk,
+                        vk_merkle_proof,
+                    },
+                    public_values,
+                })
+            })
+            .collect()
+    }
+
+    fn convert_core_proof_to_zkv(
+        &self,
+        proof: SP1ProofWithPublicValues,
+        vk: &SP1VerifyingKey,
+        stdin: &SP1Stdin,
+        opts: Option<SP1ProverOpts>,
+    ) -> Result<SP1ZkvProofWithPublicValues> {
+        let opts = opts.unwrap_or_default();
+        let compressed_proof = match proof.proof {
+            SP1Proof::Compressed(compressed_proof) => *compressed_proof,
+            SP1Proof::Core(shard_proofs) => {
+                let deferred_proofs = stdin
+                    .proofs
+                    .iter()
+                    .map(|(proof, _)| proof.clone())
+                    .collect();
+                let core_proof = SP1CoreProof {
+                    proof: SP1CoreProofData(shard_proofs),
+                    stdin: stdin.clone(),
+                    public_values: proof.public_values.clone(),
+                    // Stored proofs don't record their cycle count, which compress doesn't read.
+                    cycles: 0,
+                };
+                self.inner()
+                    .compress(vk, core_proof, deferred_proofs, opts.clone())
+                    .map_err(SP1ZkvError::Compress)?
+            }
+            _ => return Err(SP1ZkvError::UnsupportedProofFormat.into()),
+        };
+        shrink_to_zkv(
+            self.inner(),
+            compressed_proof,
+            proof.public_values.to_vec(),
+            opts,
+        )
+    }
+
+    fn convert_reduce_proof_to_zkv(
+        &self,
+        reduce_proof: SP1ReduceProof<InnerSC>,
+        public_values: Vec<u8>,
+        opts: Option<SP1ProverOpts>,
+    ) -> Result<SP1ZkvProofWithPublicValues> {
+        shrink_to_zkv(
+            self.inner(),
+            reduce_proof,
+            public_values,
+            opts.unwrap_or_default(),
+        )
+    }
+}
+
+/// Extract the compressed proof out of `proof`. `Core` proofs are rejected, as compressing them
+/// needs the program verifying key, see [`ZkvProver::convert_core_proof_to_zkv`].
+fn into_compressed(proof: SP1Proof) -> Result<SP1ReduceProof<InnerSC>> {
+    match proof {
+        SP1Proof::Compressed(compressed_proof) => Ok(*compressed_proof),
+        SP1Proof::Core(_) => Err(SP1ZkvError::MissingVerifyingKey),
+        _ => Err(SP1ZkvError::UnsupportedProofFormat),
+    }
+}
+
+/// Shrink a compressed proof and wrap it with the recursion vk Merkle proof zkVerify needs.
+fn shrink_to_zkv<C: SP1ProverComponents>(
+    prover: &SP1Prover<C>,
+    compressed_proof: SP1ReduceProof<InnerSC>,
+    public_values: Vec<u8>,
+    opts: SP1ProverOpts,
+) -> Result<SP1ZkvProofWithPublicValues> {
+    let SP1ReduceProof {
+        vk,
+        proof: shard_proof,
+    } = shrink(prover, compressed_proof, opts)?;
+    let vk_merkle_proof = make_vk_merkle_proof(prover, vk.clone(), shard_proof.clone())?;
+    Ok(SP1ZkvProofWithPublicValues {
+        proof: Proof {
+            shard_proof,
+            vk,
+            vk_merkle_proof,
+        },
+        public_values,
+    })
+}
+
+/// Shrink a compressed proof.
+fn shrink<C: SP1ProverComponents>(
+    prover: &SP1Prover<C>,
+    compressed_proof: SP1ReduceProof<InnerSC>,
+    opts: SP1ProverOpts,
+) -> Result<SP1ReduceProof<InnerSC>> {
+    prover
+        .shrink(compressed_proof, opts)
+        .map_err(SP1ZkvError::Shrink)
+}
+
+/// Build the recursion vk Merkle proof of a single shrunk proof.
+fn make_vk_merkle_proof<C: SP1ProverComponents>(
+    prover: &SP1Prover<C>,
+    vk: StarkVerifyingKey<InnerSC>,
+    shard_proof: ShardProof,
+) -> Result<MerkleProof<BabyBear, BabyBearPoseidon2>> {
+    make_vk_merkle_proofs(prover, vec![(vk, shard_proof)])?
+        .into_iter()
+        .next()
+        .ok_or(SP1ZkvError::MerkleProof(MerkleProofError::Missing))
+}
+
+/// Build the recursion vk Merkle proofs of `vks_and_proofs`, reporting prover panics, e.g. on
+/// vks missing from its vk map, as errors.
+fn make_vk_merkle_proofs<C: SP1ProverComponents>(
+    prover: &SP1Prover<C>,
+    vks_and_proofs: Vec<(StarkVerifyingKey<InnerSC>, ShardProof)>,
+) -> Result<Vec<MerkleProof<BabyBear, BabyBearPoseidon2>>, MerkleProofError> {
+    if vks_and_proofs.is_empty() {
+        return Ok(Vec::new());
     }
+    let input = SP1CompressWitnessValues {
+        vks_and_proofs,
+        is_complete: true,
+    };
+    std::panic::catch_unwind(AssertUnwindSafe(|| {
+        prover.make_merkle_proofs(input).merkle_val.vk_merkle_proofs
+    }))
+    .map_err(|panic| MerkleProofError::Panicked(panic_message(panic)))
+}
+
+/// The message of a prover panic.
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    panic
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "prover panicked".into())
 }
 
 pub fn verify(
     proof_with_public_values: &SP1ZkvProofWithPublicValues,
     vkey: &[u8; 32],
 ) -> Result<()> {
-    let proof = unsafe {
-        std::mem::transmute::<&Proof, &sp1_zkv_verifier::Proof>(&proof_with_public_values.proof)
-    };
-    sp1_zkv_verifier::verify(vkey, proof, &proof_with_public_values.public_values)?;
-    Ok(())
+    let proof = sp1_zkv_verifier::Proof::try_from(&proof_with_public_values.proof)?;
+    sp1_zkv_verifier::verify(vkey, &proof, &proof_with_public_values.public_values)
+        .map_err(SP1ZkvError::Verification)
 }
 
-#[derive(Error, Debug)]
-pub enum SP1ZkvError {
-    #[error("Unsupported proof format: only Compressed proofs are supported.")]
-    UnsupportedProofFormat,
+impl TryFrom<&Proof> for sp1_zkv_verifier::Proof {
+    type Error = SP1ZkvError;
+
+    fn try_from(proof: &Proof) -> Result<Self, Self::Error> {
+        convert(proof)
+    }
+}
+
+impl TryFrom<&sp1_zkv_verifier::Proof> for Proof {
+    type Error = SP1ZkvError;
+
+    fn try_from(proof: &sp1_zkv_verifier::Proof) -> Result<Self, Self::Error> {
+        convert(proof)
+    }
+}
+
+/// Convert between the `sp1_stark` and `sp1_stark_no_std` based proof types through their
+/// canonical bincode encoding, so that a divergence between the two is reported instead of
+/// silently misreading the proof.
+fn convert<T: Serialize, U: DeserializeOwned>(value: &T) -> Result<U> {
+    let bytes = bincode::serde::encode_to_vec(value, bincode::config::legacy())?;
+    let (converted, read) = bincode::serde::decode_from_slice(&bytes, bincode::config::legacy())?;
+    if read != bytes.len() {
+        return Err(SP1ZkvError::ProofTrailingBytes(bytes.len() - read));
+    }
+    Ok(converted)
 }
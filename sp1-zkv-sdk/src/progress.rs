@@ -0,0 +1,274 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Options to follow and bound a long running conversion, see
+//! [`ZkvProver::convert_proof_to_zkv_with`](crate::ZkvProver::convert_proof_to_zkv_with).
+//!
+//! Cancellation and timeout are checked cooperatively, before each phase and once the last one
+//! finishes, and stop the conversion with [`SP1ZkvError::Cancelled`] or
+//! [`SP1ZkvError::TimedOut`]. The prover can't be interrupted, so a phase always runs to its end:
+//! a conversion cancelled in the middle of the shrink stops once the shrink is done.
+
+use crate::{Result, SP1ZkvError};
+use sp1_stark::SP1ProverOpts;
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+/// A step of a conversion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Shrinking the compressed proof.
+    Shrink,
+    /// Building the recursion vk Merkle proof.
+    MerkleProof,
+    /// Verifying the converted proof.
+    SelfVerify,
+}
+
+/// Whether a [`Phase`] just started or finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhaseState {
+    Started,
+    Finished,
+}
+
+/// A progress report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    pub phase: Phase,
+    pub state: PhaseState,
+    /// Time elapsed since the conversion started.
+    pub elapsed: Duration,
+}
+
+/// A flag to cooperatively cancel a conversion from another thread.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask the conversions holding a clone of this token to stop.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Options of a single conversion.
+#[derive(Clone, Default)]
+pub struct ZkvConvertOpts {
+    /// Options of the shrink.
+    pub prover_opts: SP1ProverOpts,
+    /// Called when each phase starts and finishes.
+    pub progress: Option<Arc<dyn Fn(Progress) + Send + Sync>>,
+    /// Stops the conversion once cancelled.
+    pub cancellation: Option<CancellationToken>,
+    /// Wall-clock time after which the conversion stops.
+    pub timeout: Option<Duration>,
+    /// Verify the converted proof before returning it.
+    pub self_verify: bool,
+}
+
+impl ZkvConvertOpts {
+    /// Set the progress callback.
+    pub fn on_progress(mut self, progress: impl Fn(Progress) + Send + Sync + 'static) -> Self {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+}
+
+impl From<SP1ProverOpts> for ZkvConvertOpts {
+    fn from(prover_opts: SP1ProverOpts) -> Self {
+        Self {
+            prover_opts,
+            ..Default::default()
+        }
+    }
+}
+
+/// Runs the phases of a conversion according to its [`ZkvConvertOpts`].
+pub(crate) struct Tracker<'a> {
+    opts: &'a ZkvConvertOpts,
+    start: Instant,
+}
+
+impl<'a> Tracker<'a> {
+    pub(crate) fn new(opts: &'a ZkvConvertOpts) -> Self {
+        Self {
+            opts,
+            start: Instant::now(),
+        }
+    }
+
+    /// Run `phase` unless the conversion has been cancelled or has timed out, reporting its
+    /// progress.
+    pub(crate) fn run<T>(&self, phase: Phase, f: impl FnOnce() -> Result<T>) -> Result<T> {
+        self.check()?;
+        self.report(phase, PhaseState::Started);
+        let result = f()?;
+        self.report(phase, PhaseState::Finished);
+        Ok(result)
+    }
+
+    /// Fail if the conversion has been cancelled or has timed out.
+    pub(crate) fn check(&self) -> Result<()> {
+        if self
+            .opts
+            .cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            return Err(SP1ZkvError::Cancelled);
+        }
+        let elapsed = self.start.elapsed();
+        match self.opts.timeout {
+            Some(timeout) if elapsed > timeout => Err(SP1ZkvError::TimedOut(elapsed)),
+            _ => Ok(()),
+        }
+    }
+
+    fn report(&self, phase: Phase, state: PhaseState) {
+        if let Some(progress) = &self.opts.progress {
+            progress(Progress {
+                phase,
+                state,
+                elapsed: self.start.elapsed(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{sync::Mutex, thread};
+
+    #[test]
+    fn tracker_reports_phases_in_order() {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let opts = ZkvConvertOpts::default().on_progress({
+            let reports = reports.clone();
+            move |progress| {
+                reports
+                    .lock()
+                    .unwrap()
+                    .push((progress.phase, progress.state))
+            }
+        });
+        let tracker = Tracker::new(&opts);
+
+        tracker.run(Phase::Shrink, || Ok(())).unwrap();
+        tracker.run(Phase::MerkleProof, || Ok(())).unwrap();
+
+        assert_eq!(
+            *reports.lock().unwrap(),
+            [
+                (Phase::Shrink, PhaseState::Started),
+                (Phase::Shrink, PhaseState::Finished),
+                (Phase::MerkleProof, PhaseState::Started),
+                (Phase::MerkleProof, PhaseState::Finished),
+            ]
+        );
+    }
+
+    #[test]
+    fn tracker_stops_once_cancelled() {
+        let token = CancellationToken::new();
+        let opts = ZkvConvertOpts {
+            cancellation: Some(token.clone()),
+            ..Default::default()
+        };
+        let tracker = Tracker::new(&opts);
+
+        tracker.run(Phase::Shrink, || Ok(())).unwrap();
+        token.cancel();
+        let error = tracker.run(Phase::MerkleProof, || Ok(())).unwrap_err();
+
+        assert!(matches!(error, SP1ZkvError::Cancelled));
+    }
+
+    #[test]
+    fn tracker_stops_after_timeout() {
+        let opts = ZkvConvertOpts {
+            timeout: Some(Duration::ZERO),
+            ..Default::default()
+        };
+        let tracker = Tracker::new(&opts);
+        thread::sleep(Duration::from_millis(1));
+
+        let error = tracker.run(Phase::Shrink, || Ok(())).unwrap_err();
+
+        assert!(matches!(error, SP1ZkvError::TimedOut(_)));
+    }
+
+    #[test]
+    fn tracker_stops_once_the_phase_cancelled_in_finishes() {
+        let token = CancellationToken::new();
+        let opts = ZkvConvertOpts {
+            cancellation: Some(token.clone()),
+            ..Default::default()
+        };
+        let tracker = Tracker::new(&opts);
+
+        tracker
+            .run(Phase::Shrink, || {
+                token.cancel();
+                Ok(())
+            })
+            .unwrap();
+        let error = tracker.check().unwrap_err();
+
+        assert!(matches!(error, SP1ZkvError::Cancelled));
+    }
+
+    #[test]
+    fn tracker_stops_once_the_phase_timed_out_in_finishes() {
+        let opts = ZkvConvertOpts {
+            timeout: Some(Duration::from_millis(10)),
+            ..Default::default()
+        };
+        let tracker = Tracker::new(&opts);
+
+        tracker
+            .run(Phase::Shrink, || {
+                thread::sleep(Duration::from_millis(50));
+                Ok(())
+            })
+            .unwrap();
+        let error = tracker.run(Phase::MerkleProof, || Ok(())).unwrap_err();
+
+        assert!(matches!(error, SP1ZkvError::TimedOut(_)));
+    }
+
+    #[test]
+    #[should_panic(expected = "phase panicked")]
+    fn tracker_propagates_phase_panics() {
+        let opts = ZkvConvertOpts::default();
+        let tracker = Tracker::new(&opts);
+
+        let _ = tracker.run(Phase::Shrink, || -> Result<()> { panic!("phase panicked") });
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Result, SP1ZkvError, SP1ZkvProofWithPublicValues, ZkvProver};
+use sp1_prover::components::SP1ProverComponents;
+use sp1_sdk::{
+    Prover, SP1Proof, SP1ProofMode, SP1ProofWithPublicValues, cpu::CpuProveBuilder,
+    cuda::CudaProveBuilder, env::EnvProveBuilder,
+};
+use sp1_stark::SP1ProverOpts;
+use std::marker::PhantomData;
+
+/// An sp1 sdk prove request, which can be run straight into a zkVerify proof.
+pub trait ZkvProveBuilder: Sized {
+    /// Run the request as configured, like its own `run`.
+    fn run_request(self) -> anyhow::Result<SP1ProofWithPublicValues>;
+
+    /// Prove the program straight into a zkVerify proof with `prover`, the one the request comes
+    /// from, i.e. `prover.prove(&pk, &stdin).compressed().zkv(&prover).run()` in place of a
+    /// compressed proof followed by [`convert_proof_to_zkv`](ZkvProver::convert_proof_to_zkv).
+    ///
+    /// The proof mode of the request is left as is and must be `Compressed`.
+    fn zkv<P: Prover<C>, C: SP1ProverComponents>(
+        self,
+        prover: &P,
+    ) -> ZkvProveRequest<'_, Self, P, C> {
+        ZkvProveRequest {
+            builder: self,
+            prover,
+            opts: None,
+            _components: PhantomData,
+        }
+    }
+}
+
+macro_rules! impl_zkv_prove_builder {
+    ($($builder:ty),* $(,)?) => {
+        $(
+            impl ZkvProveBuilder for $builder {
+                fn run_request(self) -> anyhow::Result<SP1ProofWithPublicValues> {
+                    self.run()
+                }
+            }
+        )*
+    };
+}
+
+impl_zkv_prove_builder!(
+    CpuProveBuilder<'_>,
+    CudaProveBuilder<'_>,
+    EnvProveBuilder<'_>
+);
+
+#[cfg(feature = "network")]
+impl_zkv_prove_builder!(sp1_sdk::network::NetworkProveBuilder<'_>);
+
+/// A request to prove the execution of a program straight into a zkVerify proof, created by
+/// [`ZkvProveBuilder::zkv`].
+pub struct ZkvProveRequest<'a, B, P, C> {
+    builder: B,
+    prover: &'a P,
+    opts: Option<SP1ProverOpts>,
+    _components: PhantomData<C>,
+}
+
+impl<B, P, C> ZkvProveRequest<'_, B, P, C>
+where
+    B: ZkvProveBuilder,
+    P: Prover<C>,
+    C: SP1ProverComponents,
+{
+    /// Set the options of the shrink step.
+    pub fn opts(mut self, opts: SP1ProverOpts) -> Self {
+        self.opts = Some(opts);
+        self
+    }
+
+    /// Execute the program, compress and shrink its proof, returning the zkVerify proof along with
+    /// the vk digest of the program.
+    ///
+    /// A request in any proof mode other than `Compressed` fails with
+    /// [`SP1ZkvError::ProofMode`] rather than being switched to it. The sdk requests don't expose
+    /// their mode, so it is only checked once the request has run.
+    pub fn run(self) -> Result<(SP1ZkvProofWithPublicValues, [u8; 32])> {
+        let proof = self.builder.run_request().map_err(SP1ZkvError::Prove)?;
+        let mode = match &proof.proof {
+            SP1Proof::Compressed(_) => None,
+            SP1Proof::Core(_) => Some(SP1ProofMode::Core),
+            SP1Proof::Plonk(_) => Some(SP1ProofMode::Plonk),
+            SP1Proof::Groth16(_) => Some(SP1ProofMode::Groth16),
+        };
+        if let Some(mode) = mode {
+            return Err(SP1ZkvError::ProofMode(mode));
+        }
+        let zkv_proof = ZkvProver::<C>::convert_proof_to_zkv(self.prover, proof, self.opts)?;
+        let vkey_digest = sp1_zkv_verifier::claimed_vkey_digest(
+            &sp1_zkv_verifier::Proof::try_from(&zkv_proof.proof)?,
+        );
+        Ok((zkv_proof, vkey_digest))
+    }
+}
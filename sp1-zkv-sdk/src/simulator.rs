@@ -0,0 +1,245 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An in-process stand-in for the zkVerify verification and aggregation pallets.
+//!
+//! [`Simulator`] follows the on-chain flow up to the chain boundary: vk registration, proof
+//! submission and verification, statement hashing and aggregation of the verified statements in a
+//! Keccak binary Merkle tree. Everything is deterministic, so it can back integration tests of a
+//! submission pipeline without running a node.
+
+use crate::{SP1ZkvProofWithPublicValues, VkOrHash};
+use sha3::{Digest, Keccak256};
+use std::collections::BTreeSet;
+use thiserror::Error;
+
+/// A 32 bytes Keccak digest.
+pub type Hash = [u8; 32];
+
+/// Events emitted by the [`Simulator`], mirroring the pallet ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// A vk has been registered.
+    VkRegistered { hash: Hash },
+    /// A proof has been verified and its statement queued for aggregation.
+    ProofVerified { statement: Hash },
+    /// The queued statements have been aggregated.
+    AggregationComplete { id: u64, root: Hash },
+}
+
+#[derive(Error, Debug)]
+pub enum SimulatorError {
+    #[error("Vk {} has not been registered.", hex::encode(.0))]
+    VkNotRegistered(Hash),
+    #[error("Vk {} is already registered.", hex::encode(.0))]
+    VkAlreadyRegistered(Hash),
+    #[error("Proof verification failed: {0}")]
+    VerificationFailed(crate::SP1ZkvError),
+    #[error("No statements to aggregate.")]
+    EmptyAggregation,
+}
+
+/// Inclusion proof of a statement in an [`Aggregation`], laid out like `binary_merkle_tree`'s
+/// `MerkleProof`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InclusionProof {
+    /// Root of the aggregation tree.
+    pub root: Hash,
+    /// Sibling hashes from the leaf up to the root. Levels where the node is promoted have none.
+    pub proof: Vec<Hash>,
+    /// Number of statements in the aggregation.
+    pub number_of_leaves: usize,
+    /// Position of the statement in the aggregation.
+    pub leaf_index: usize,
+    /// The aggregated statement.
+    pub leaf: Hash,
+}
+
+impl InclusionProof {
+    /// Check this proof against its root, with the same rules as
+    /// [`is_statement_included`](crate::is_statement_included).
+    pub fn is_valid(&self) -> bool {
+        crate::aggregation_root(
+            Keccak256::digest(self.leaf).into(),
+            &self.proof,
+            self.leaf_index,
+            self.number_of_leaves,
+        ) == Some(self.root)
+    }
+}
+
+/// A batch of statements aggregated in a Keccak binary Merkle tree.
+///
+/// Leaves are the Keccak hashes of the statements and inner nodes the Keccak hash of their
+/// children concatenated. A node without sibling is promoted to the next level unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Aggregation {
+    /// Sequential id of the aggregation.
+    pub id: u64,
+    /// The aggregated statements, in submission order.
+    pub statements: Vec<Hash>,
+    /// Root of the aggregation tree.
+    pub root: Hash,
+}
+
+impl Aggregation {
+    /// Aggregate `statements`, computing the root of their tree.
+    pub fn new(id: u64, statements: Vec<Hash>) -> Self {
+        let root = merkle_levels(&statements)
+            .last()
+            .map(|level| level[0])
+            .unwrap_or_default();
+        Self {
+            id,
+            statements,
+            root,
+        }
+    }
+
+    /// Inclusion proof of the statement at `leaf_index`, if any.
+    pub fn proof(&self, leaf_index: usize) -> Option<InclusionProof> {
+        let leaf = *self.statements.get(leaf_index)?;
+        let levels = merkle_levels(&self.statements);
+        let mut index = leaf_index;
+        let mut proof = Vec::with_capacity(levels.len());
+        for level in &levels[..levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+        Some(InclusionProof {
+            root: self.root,
+            proof,
+            number_of_leaves: self.statements.len(),
+            leaf_index,
+            leaf,
+        })
+    }
+
+    /// Inclusion proof of `statement`, if it is part of this aggregation.
+    pub fn proof_of(&self, statement: &Hash) -> Option<InclusionProof> {
+        self.proof(self.statements.iter().position(|s| s == statement)?)
+    }
+}
+
+/// All the levels of the aggregation tree over `statements`, from the leaves to the root.
+fn merkle_levels(statements: &[Hash]) -> Vec<Vec<Hash>> {
+    if statements.is_empty() {
+        return Vec::new();
+    }
+    let mut levels = vec![
+        statements
+            .iter()
+            .map(|s| Keccak256::digest(s).into())
+            .collect::<Vec<Hash>>(),
+    ];
+    while levels[levels.len() - 1].len() > 1 {
+        let next = levels[levels.len() - 1]
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => Keccak256::new()
+                    .chain_update(left)
+                    .chain_update(right)
+                    .finalize()
+                    .into(),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+        levels.push(next);
+    }
+    levels
+}
+
+/// In-process simulation of the zkVerify SP1 verification and aggregation flow.
+#[derive(Debug, Default)]
+pub struct Simulator {
+    vks: BTreeSet<Hash>,
+    pending: Vec<Hash>,
+    aggregations: Vec<Aggregation>,
+    events: Vec<Event>,
+}
+
+impl Simulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the SP1 program vk digest `vk`, returning its hash.
+    pub fn register_vk(&mut self, vk: &[u8; 32]) -> Result<Hash, SimulatorError> {
+        // The vk is already a digest of the program, so it is its own hash.
+        let hash = *vk;
+        if !self.vks.insert(hash) {
+            return Err(SimulatorError::VkAlreadyRegistered(hash));
+        }
+        self.events.push(Event::VkRegistered { hash });
+        Ok(hash)
+    }
+
+    /// Verify `proof` against `vk` and queue its statement for the next aggregation, returning
+    /// the statement hash.
+    pub fn submit_proof(
+        &mut self,
+        vk: VkOrHash,
+        proof: &SP1ZkvProofWithPublicValues,
+    ) -> Result<Hash, SimulatorError> {
+        let vk_hash = match vk {
+            VkOrHash::Vk(vk) => vk,
+            VkOrHash::Hash(hash) if self.vks.contains(&hash) => hash,
+            VkOrHash::Hash(hash) => return Err(SimulatorError::VkNotRegistered(hash)),
+        };
+        crate::verify(proof, &vk_hash).map_err(SimulatorError::VerificationFailed)?;
+        let statement = proof.statement_hash(&vk_hash);
+        self.pending.push(statement);
+        self.events.push(Event::ProofVerified { statement });
+        Ok(statement)
+    }
+
+    /// Aggregate all the statements submitted since the previous aggregation.
+    pub fn aggregate(&mut self) -> Result<&Aggregation, SimulatorError> {
+        if self.pending.is_empty() {
+            return Err(SimulatorError::EmptyAggregation);
+        }
+        let id = self.aggregations.len() as u64 + 1;
+        let aggregation = Aggregation::new(id, std::mem::take(&mut self.pending));
+        self.events.push(Event::AggregationComplete {
+            id,
+            root: aggregation.root,
+        });
+        self.aggregations.push(aggregation);
+        Ok(&self.aggregations[self.aggregations.len() - 1])
+    }
+
+    /// The aggregation with the given `id`, if any.
+    pub fn aggregation(&self, id: u64) -> Option<&Aggregation> {
+        self.aggregations.get(id.checked_sub(1)? as usize)
+    }
+
+    /// Statements waiting for the next aggregation.
+    pub fn pending(&self) -> &[Hash] {
+        &self.pending
+    }
+
+    /// All the events emitted so far.
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Drain the events emitted so far.
+    pub fn take_events(&mut self) -> Vec<Event> {
+        std::mem::take(&mut self.events)
+    }
+}
@@ -0,0 +1,124 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Payloads for zkVerify's `submitProof` extrinsic.
+//!
+//! A [`Submission`] holds the proof, public inputs and vk exactly as the SP1 verifier pallet
+//! expects them, and encodes them either as the JSON accepted by JavaScript and relayer clients or
+//! as the SCALE-encoded arguments of the `submitProof` call.
+
+use crate::{Result, SP1ZkvProofWithPublicValues};
+use codec::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+/// Proof type of SP1 proofs in zkVerify clients.
+pub const PROOF_TYPE: &str = "sp1";
+
+/// The verification key of a submission, either inline or as the hash of a registered one.
+///
+/// Its SCALE encoding matches the pallet `VkOrHash`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub enum VkOrHash {
+    /// An SP1 program vk digest.
+    Vk([u8; 32]),
+    /// The hash of a registered vk, see
+    /// [`Simulator::register_vk`](crate::simulator::Simulator::register_vk).
+    Hash([u8; 32]),
+}
+
+/// Arguments of a `submitProof` call to the SP1 verifier pallet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    /// The program vk digest, or the hash of a registered one.
+    pub vk: VkOrHash,
+    /// The proof, bincode encoded like [`sp1_zkv_verifier::Proof`].
+    pub proof: Vec<u8>,
+    /// The public inputs.
+    pub pubs: Vec<u8>,
+    /// The domain to aggregate the proof in, if any.
+    pub domain_id: Option<u32>,
+}
+
+/// JSON payload of a submission, with `0x` prefixed hex fields.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonPayload {
+    pub proof_type: String,
+    pub vk_registered: bool,
+    pub proof_data: JsonProofData,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain_id: Option<u32>,
+}
+
+/// The `proofData` field of a [`JsonPayload`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonProofData {
+    pub proof: String,
+    pub public_signals: String,
+    pub vk: String,
+}
+
+impl Submission {
+    /// Submission of `proof` against `vk`, not aggregated in any domain.
+    pub fn new(proof: &SP1ZkvProofWithPublicValues, vk: VkOrHash) -> Result<Self> {
+        Ok(Self {
+            vk,
+            proof: bincode::serde::encode_to_vec(&proof.proof, bincode::config::legacy())?,
+            pubs: proof.public_values.clone(),
+            domain_id: None,
+        })
+    }
+
+    /// Aggregate the proof in the domain `domain_id`.
+    pub fn with_domain_id(mut self, domain_id: u32) -> Self {
+        self.domain_id = Some(domain_id);
+        self
+    }
+
+    /// The JSON payload of this submission.
+    pub fn json_payload(&self) -> JsonPayload {
+        let (vk_registered, vk) = match &self.vk {
+            VkOrHash::Vk(vk) => (false, vk),
+            VkOrHash::Hash(hash) => (true, hash),
+        };
+        JsonPayload {
+            proof_type: PROOF_TYPE.into(),
+            vk_registered,
+            proof_data: JsonProofData {
+                proof: to_hex(&self.proof),
+                public_signals: to_hex(&self.pubs),
+                vk: to_hex(vk),
+            },
+            domain_id: self.domain_id,
+        }
+    }
+
+    /// The JSON payload of this submission, serialized.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.json_payload())
+            .expect("the payload only holds strings, booleans and integers")
+    }
+
+    /// The SCALE encoding of the `submitProof` call arguments: vk or hash, proof, public inputs and
+    /// optional domain id, in this order.
+    pub fn encode_call_args(&self) -> Vec<u8> {
+        (&self.vk, &self.proof, &self.pubs, &self.domain_id).encode()
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
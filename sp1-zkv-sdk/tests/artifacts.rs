@@ -0,0 +1,77 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use common::valid_proof;
+use rstest::rstest;
+use sp1_core_executor::SP1ReduceProof;
+use sp1_zkv_sdk::{
+    SP1ZkvProofWithPublicValues,
+    artifacts::{load_public_values, load_reduce_proof},
+};
+use std::path::PathBuf;
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("sp1-zkv-sdk-{}-{name}", std::process::id()))
+}
+
+#[rstest]
+fn load_saved_reduce_proof(valid_proof: SP1ZkvProofWithPublicValues) {
+    // A shrink proof is itself a reduce proof, which makes for a real artifact.
+    let reduce_proof = SP1ReduceProof {
+        vk: valid_proof.proof.vk,
+        proof: valid_proof.proof.shard_proof,
+    };
+    let bytes = bincode::serde::encode_to_vec(&reduce_proof, bincode::config::legacy()).unwrap();
+    let path = temp_path("reduce_proof.bin");
+    std::fs::write(&path, &bytes).unwrap();
+
+    let loaded = load_reduce_proof(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        bincode::serde::encode_to_vec(&loaded, bincode::config::legacy()).unwrap(),
+        bytes
+    );
+}
+
+#[rstest]
+fn load_saved_public_values(valid_proof: SP1ZkvProofWithPublicValues) {
+    let path = temp_path("public_values.bin");
+    std::fs::write(&path, &valid_proof.public_values).unwrap();
+
+    let loaded = load_public_values(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded, valid_proof.public_values);
+}
+
+#[test]
+fn report_missing_artifact() {
+    let error = load_reduce_proof(temp_path("missing.bin")).unwrap_err();
+    assert!(error.to_string().contains("missing.bin"));
+}
+
+#[test]
+fn report_malformed_artifact() {
+    let path = temp_path("malformed.bin");
+    std::fs::write(&path, [1, 2, 3]).unwrap();
+
+    let result = load_reduce_proof(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(result.is_err());
+}
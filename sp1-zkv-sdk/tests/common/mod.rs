@@ -0,0 +1,45 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fixtures shared by the integration tests, built from the reference proof of the verifier.
+
+// Each test binary only uses some of the fixtures.
+#![allow(dead_code)]
+
+use rstest::fixture;
+use sp1_zkv_sdk::SP1ZkvProofWithPublicValues;
+
+/// The reference proof, in the legacy bincode encoding.
+pub const PROOF_PATH: &str = "../sp1-zkv-verifier/resources/proof.bin";
+
+#[fixture]
+pub fn proof_bytes() -> Vec<u8> {
+    std::fs::read(PROOF_PATH).unwrap()
+}
+
+#[fixture]
+pub fn valid_proof(proof_bytes: Vec<u8>) -> SP1ZkvProofWithPublicValues {
+    bincode::serde::decode_from_slice(&proof_bytes, bincode::config::legacy())
+        .unwrap()
+        .0
+}
+
+#[fixture]
+pub fn valid_vk_hash() -> [u8; 32] {
+    hex::decode("45946758049372c74bceb6ba6526a9661b1915a3403125e470d5ff7f6c15c0dd")
+        .unwrap()
+        .try_into()
+        .unwrap()
+}
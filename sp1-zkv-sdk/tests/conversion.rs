@@ -0,0 +1,55 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use common::{proof_bytes, valid_proof, valid_vk_hash};
+use rstest::rstest;
+use sp1_zkv_sdk::{Proof, SP1ZkvProofWithPublicValues, verify};
+
+#[rstest]
+fn round_trip_through_the_no_std_proof(
+    proof_bytes: Vec<u8>,
+    valid_proof: SP1ZkvProofWithPublicValues,
+    valid_vk_hash: [u8; 32],
+) {
+    let no_std_proof = sp1_zkv_verifier::Proof::try_from(&valid_proof.proof).unwrap();
+    assert!(
+        sp1_zkv_verifier::verify(&valid_vk_hash, &no_std_proof, &valid_proof.public_values).is_ok()
+    );
+
+    let round_tripped = SP1ZkvProofWithPublicValues {
+        proof: Proof::try_from(&no_std_proof).unwrap(),
+        public_values: valid_proof.public_values,
+    };
+    assert_eq!(
+        bincode::serde::encode_to_vec(&round_tripped, bincode::config::legacy()).unwrap(),
+        proof_bytes
+    );
+}
+
+#[rstest]
+fn verify_converted_proof(valid_proof: SP1ZkvProofWithPublicValues, valid_vk_hash: [u8; 32]) {
+    assert!(verify(&valid_proof, &valid_vk_hash).is_ok());
+}
+
+#[rstest]
+fn reject_converted_proof_with_wrong_vk(
+    valid_proof: SP1ZkvProofWithPublicValues,
+    mut valid_vk_hash: [u8; 32],
+) {
+    valid_vk_hash[0] ^= 1;
+    assert!(verify(&valid_proof, &valid_vk_hash).is_err());
+}
@@ -0,0 +1,228 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use common::{PROOF_PATH as LEGACY_PROOF, valid_proof, valid_vk_hash};
+use rstest::{fixture, rstest};
+use sp1_zkv_sdk::{
+    ProofMetadata, SP1ZkvError, SP1ZkvProofWithPublicValues,
+    envelope::{FORMAT_VERSION, MAGIC},
+};
+use std::path::PathBuf;
+
+#[fixture]
+fn metadata(valid_vk_hash: [u8; 32]) -> ProofMetadata {
+    ProofMetadata {
+        sp1_version: "v5.0.0".into(),
+        vkey_digest: valid_vk_hash,
+        created_at: 1_700_000_000,
+        program_name: Some("sp1-program".into()),
+    }
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "sp1-zkv-sdk-envelope-{}-{name}",
+        std::process::id()
+    ))
+}
+
+fn encode(proof: &SP1ZkvProofWithPublicValues) -> Vec<u8> {
+    bincode::serde::encode_to_vec(proof, bincode::config::legacy()).unwrap()
+}
+
+#[rstest]
+fn save_and_load_with_metadata(valid_proof: SP1ZkvProofWithPublicValues, metadata: ProofMetadata) {
+    let path = temp_path("proof.bin");
+    valid_proof.save_with_metadata(&path, &metadata).unwrap();
+
+    let (loaded, loaded_metadata) = SP1ZkvProofWithPublicValues::load_with_metadata(&path).unwrap();
+    let loaded_without_metadata = SP1ZkvProofWithPublicValues::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(encode(&loaded), encode(&valid_proof));
+    assert_eq!(encode(&loaded_without_metadata), encode(&valid_proof));
+    assert_eq!(loaded_metadata, metadata);
+}
+
+#[rstest]
+fn save_in_the_documented_layout(
+    valid_proof: SP1ZkvProofWithPublicValues,
+    metadata: ProofMetadata,
+) {
+    let path = temp_path("layout.bin");
+    valid_proof.save_with_metadata(&path, &metadata).unwrap();
+    let bytes = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    // Magic, version, metadata and the checksum length.
+    let header = concat!(
+        "5350315a4b56",
+        "0100",
+        "060000000000000076352e302e30",
+        "200000000000000045946758049372c74bceb6ba6526a9661b1915a3403125e470d5ff7f6c15c0dd",
+        "00f1536500000000",
+        "010b000000000000007370312d70726f6772616d",
+        "2000000000000000"
+    );
+    assert_eq!(hex::encode(&bytes[..header.len() / 2]), header);
+    // The checksum is the Keccak-256 of the legacy encoding of the other fields, recomputed with
+    // `openssl dgst -keccak-256` over the header without magic and checksum, then the proof.
+    assert_eq!(
+        hex::encode(&bytes[header.len() / 2..header.len() / 2 + 32]),
+        "7eb63d6cc2be445375c3d5a2c5fb1a76668df1f80993e9781c0e54ba15ecbd6e"
+    );
+    // The length prefixed proof, then its public values as in the legacy encoding.
+    let proof = &bytes[header.len() / 2 + 32..];
+    assert_eq!(proof[..8], 670295u64.to_le_bytes());
+    assert_eq!(proof[8..], std::fs::read(LEGACY_PROOF).unwrap());
+}
+
+#[rstest]
+fn default_metadata_describes_the_proof(
+    valid_proof: SP1ZkvProofWithPublicValues,
+    valid_vk_hash: [u8; 32],
+) {
+    let metadata = ProofMetadata::new(&valid_proof)
+        .unwrap()
+        .with_program_name("sp1-program");
+
+    assert_eq!(metadata.vkey_digest, valid_vk_hash);
+    assert_eq!(metadata.sp1_version, sp1_sdk::SP1_CIRCUIT_VERSION);
+    assert!(metadata.created_at > 0);
+    assert_eq!(metadata.program_name.as_deref(), Some("sp1-program"));
+}
+
+#[rstest]
+fn load_legacy_file(valid_proof: SP1ZkvProofWithPublicValues) {
+    let loaded = SP1ZkvProofWithPublicValues::load(LEGACY_PROOF).unwrap();
+
+    assert_eq!(encode(&loaded), encode(&valid_proof));
+    assert!(matches!(
+        SP1ZkvProofWithPublicValues::load_with_metadata(LEGACY_PROOF),
+        Err(SP1ZkvError::MissingMagic(_))
+    ));
+}
+
+#[rstest]
+fn reject_corrupted_file(valid_proof: SP1ZkvProofWithPublicValues, metadata: ProofMetadata) {
+    let path = temp_path("corrupted.bin");
+    valid_proof.save_with_metadata(&path, &metadata).unwrap();
+    let mut bytes = std::fs::read(&path).unwrap();
+    *bytes.last_mut().unwrap() ^= 1;
+    std::fs::write(&path, bytes).unwrap();
+
+    let result = SP1ZkvProofWithPublicValues::load(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(matches!(result, Err(SP1ZkvError::ChecksumMismatch)));
+}
+
+#[rstest]
+fn reject_trailing_bytes(valid_proof: SP1ZkvProofWithPublicValues, metadata: ProofMetadata) {
+    let path = temp_path("trailing.bin");
+    valid_proof.save_with_metadata(&path, &metadata).unwrap();
+    let mut bytes = std::fs::read(&path).unwrap();
+    bytes.push(0);
+    std::fs::write(&path, bytes).unwrap();
+
+    let result = SP1ZkvProofWithPublicValues::load_with_metadata(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(matches!(result, Err(SP1ZkvError::ProofTrailingBytes(1))));
+}
+
+#[rstest]
+fn reject_trailing_bytes_in_legacy_file(valid_proof: SP1ZkvProofWithPublicValues) {
+    let path = temp_path("legacy_trailing.bin");
+    let mut bytes = encode(&valid_proof);
+    bytes.push(0);
+    std::fs::write(&path, bytes).unwrap();
+
+    let result = SP1ZkvProofWithPublicValues::load(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(matches!(result, Err(SP1ZkvError::ProofTrailingBytes(1))));
+}
+
+#[test]
+fn reject_unknown_format_version() {
+    let path = temp_path("future.bin");
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend((FORMAT_VERSION + 1).to_le_bytes());
+    std::fs::write(&path, bytes).unwrap();
+
+    let result = SP1ZkvProofWithPublicValues::load(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(matches!(
+        result,
+        Err(SP1ZkvError::UnsupportedFormatVersion(version)) if version == FORMAT_VERSION + 1
+    ));
+}
+
+#[rstest]
+fn json_round_trip(valid_proof: SP1ZkvProofWithPublicValues, metadata: ProofMetadata) {
+    let json = valid_proof.to_json(&metadata).unwrap();
+
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["version"], 1);
+    assert_eq!(value["metadata"]["sp1_version"], "v5.0.0");
+    assert_eq!(
+        value["metadata"]["vkey_digest"],
+        "0x45946758049372c74bceb6ba6526a9661b1915a3403125e470d5ff7f6c15c0dd"
+    );
+    assert_eq!(value["metadata"]["created_at"], 1_700_000_000);
+    assert_eq!(value["metadata"]["program_name"], "sp1-program");
+    assert_eq!(
+        value["checksum"],
+        "0x7eb63d6cc2be445375c3d5a2c5fb1a76668df1f80993e9781c0e54ba15ecbd6e"
+    );
+    assert_eq!(
+        value["public_values"],
+        "0xb94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+    );
+
+    let (decoded, decoded_metadata) = SP1ZkvProofWithPublicValues::from_json(&json).unwrap();
+    assert_eq!(encode(&decoded), encode(&valid_proof));
+    assert_eq!(decoded_metadata, metadata);
+}
+
+#[rstest]
+fn reject_tampered_json(valid_proof: SP1ZkvProofWithPublicValues, metadata: ProofMetadata) {
+    let json = valid_proof
+        .to_json(&metadata)
+        .unwrap()
+        .replace("0xb94d27b9", "0xb94d27b8");
+
+    assert!(matches!(
+        SP1ZkvProofWithPublicValues::from_json(&json),
+        Err(SP1ZkvError::ChecksumMismatch)
+    ));
+}
+
+#[rstest]
+fn reject_tampered_metadata(valid_proof: SP1ZkvProofWithPublicValues, metadata: ProofMetadata) {
+    let json = valid_proof
+        .to_json(&metadata)
+        .unwrap()
+        .replace("sp1-program", "sp1-programs");
+
+    assert!(matches!(
+        SP1ZkvProofWithPublicValues::from_json(&json),
+        Err(SP1ZkvError::ChecksumMismatch)
+    ));
+}
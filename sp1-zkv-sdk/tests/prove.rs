@@ -0,0 +1,118 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use rstest::{fixture, rstest};
+use sp1_elf::SP1_ELF;
+use sp1_sdk::{CpuProver, HashableKey, Prover, ProverClient, SP1ProofMode, SP1Stdin};
+use sp1_zkv_sdk::{SP1ZkvError, ZkvBatchOpts, ZkvProveBuilder, ZkvProver, verify};
+
+#[fixture]
+fn prover() -> CpuProver {
+    ProverClient::builder().cpu().build()
+}
+
+#[fixture]
+fn stdin() -> SP1Stdin {
+    let mut stdin = SP1Stdin::new();
+    stdin.write(&b"hello world".to_vec());
+    stdin
+}
+
+#[rstest]
+fn keep_the_options_of_the_sdk_prove_request(prover: CpuProver, stdin: SP1Stdin) {
+    let (pk, _) = prover.setup(SP1_ELF);
+
+    assert!(matches!(
+        prover
+            .prove(&pk, &stdin)
+            .compressed()
+            .cycle_limit(1)
+            .zkv(&prover)
+            .run(),
+        Err(SP1ZkvError::Prove(_))
+    ));
+}
+
+#[rstest]
+fn prove_a_verifiable_zkv_proof(prover: CpuProver, stdin: SP1Stdin) {
+    let (pk, vk) = prover.setup(SP1_ELF);
+
+    let (zkv_proof, vkey_digest) = prover
+        .prove(&pk, &stdin)
+        .compressed()
+        .zkv(&prover)
+        .run()
+        .unwrap();
+
+    assert_eq!(vkey_digest, vk.hash_bytes());
+    assert!(verify(&zkv_proof, &vkey_digest).is_ok());
+}
+
+#[rstest]
+fn reject_a_prove_request_in_another_mode(prover: CpuProver, stdin: SP1Stdin) {
+    let (pk, _) = prover.setup(SP1_ELF);
+
+    assert!(matches!(
+        prover.prove(&pk, &stdin).core().zkv(&prover).run(),
+        Err(SP1ZkvError::ProofMode(SP1ProofMode::Core))
+    ));
+}
+
+#[rstest]
+fn convert_core_proof_given_its_vk_and_stdin(prover: CpuProver, stdin: SP1Stdin) {
+    let (pk, vk) = prover.setup(SP1_ELF);
+    let proof = prover.prove(&pk, &stdin).core().run().unwrap();
+
+    let zkv_proof = prover
+        .convert_core_proof_to_zkv(proof, &vk, &stdin, None)
+        .unwrap();
+
+    assert!(verify(&zkv_proof, &vk.hash_bytes()).is_ok());
+}
+
+#[rstest]
+fn reject_core_proof_without_its_vk(prover: CpuProver, stdin: SP1Stdin) {
+    let (pk, _) = prover.setup(SP1_ELF);
+    let proof = prover.prove(&pk, &stdin).core().run().unwrap();
+
+    assert!(matches!(
+        prover.convert_proof_to_zkv(proof, None),
+        Err(SP1ZkvError::MissingVerifyingKey)
+    ));
+}
+
+#[rstest]
+fn convert_each_proof_of_a_batch(prover: CpuProver, stdin: SP1Stdin) {
+    let (pk, vk) = prover.setup(SP1_ELF);
+    let compressed = prover.prove(&pk, &stdin).compressed().run().unwrap();
+    let core = prover.prove(&pk, &stdin).core().run().unwrap();
+    let opts = ZkvBatchOpts {
+        memory_budget: Some(u64::MAX),
+        ..Default::default()
+    };
+
+    let results =
+        prover.convert_proofs_to_zkv(vec![compressed.clone(), core, compressed], Some(opts));
+
+    assert_eq!(results.len(), 3);
+    assert!(verify(results[0].as_ref().unwrap(), &vk.hash_bytes()).is_ok());
+    assert!(matches!(results[1], Err(SP1ZkvError::MissingVerifyingKey)));
+    assert!(verify(results[2].as_ref().unwrap(), &vk.hash_bytes()).is_ok());
+}
+
+#[rstest]
+fn convert_empty_batch(prover: CpuProver) {
+    assert!(prover.convert_proofs_to_zkv(vec![], None).is_empty());
+}
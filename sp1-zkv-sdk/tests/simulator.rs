@@ -0,0 +1,146 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use common::{valid_proof, valid_vk_hash};
+use rstest::rstest;
+use sp1_zkv_sdk::{
+    SP1ZkvProofWithPublicValues, VkOrHash, is_statement_included,
+    simulator::{Aggregation, Event, Simulator, SimulatorError},
+    statement_hash,
+};
+
+#[rstest]
+fn verify_and_aggregate_registered_proof(
+    valid_proof: SP1ZkvProofWithPublicValues,
+    valid_vk_hash: [u8; 32],
+) {
+    let mut simulator = Simulator::new();
+    let vk_hash = simulator.register_vk(&valid_vk_hash).unwrap();
+
+    let statement = simulator
+        .submit_proof(VkOrHash::Hash(vk_hash), &valid_proof)
+        .unwrap();
+    assert_eq!(
+        statement,
+        statement_hash(&valid_vk_hash, &valid_proof.public_values)
+    );
+    assert_eq!(simulator.pending(), &[statement]);
+
+    let aggregation = simulator.aggregate().unwrap().clone();
+    let inclusion = aggregation.proof_of(&statement).unwrap();
+    assert!(inclusion.is_valid());
+    assert!(is_statement_included(
+        &valid_vk_hash,
+        &valid_proof.public_values,
+        &inclusion.proof,
+        inclusion.leaf_index,
+        inclusion.number_of_leaves,
+        &aggregation.root
+    ));
+    assert!(simulator.pending().is_empty());
+    assert_eq!(simulator.aggregation(aggregation.id), Some(&aggregation));
+
+    assert_eq!(
+        simulator.take_events(),
+        vec![
+            Event::VkRegistered { hash: vk_hash },
+            Event::ProofVerified { statement },
+            Event::AggregationComplete {
+                id: 1,
+                root: aggregation.root
+            },
+        ]
+    );
+}
+
+#[rstest]
+fn accept_inline_vk(valid_proof: SP1ZkvProofWithPublicValues, valid_vk_hash: [u8; 32]) {
+    let mut simulator = Simulator::new();
+
+    assert!(
+        simulator
+            .submit_proof(VkOrHash::Vk(valid_vk_hash), &valid_proof)
+            .is_ok()
+    );
+}
+
+#[rstest]
+fn reject_unregistered_vk_hash(valid_proof: SP1ZkvProofWithPublicValues, valid_vk_hash: [u8; 32]) {
+    let mut simulator = Simulator::new();
+
+    assert!(matches!(
+        simulator.submit_proof(VkOrHash::Hash(valid_vk_hash), &valid_proof),
+        Err(SimulatorError::VkNotRegistered(_))
+    ));
+}
+
+#[rstest]
+fn reject_vk_registered_twice(valid_vk_hash: [u8; 32]) {
+    let mut simulator = Simulator::new();
+    simulator.register_vk(&valid_vk_hash).unwrap();
+
+    assert!(matches!(
+        simulator.register_vk(&valid_vk_hash),
+        Err(SimulatorError::VkAlreadyRegistered(_))
+    ));
+}
+
+#[rstest]
+fn not_queue_invalid_proof(mut valid_proof: SP1ZkvProofWithPublicValues, valid_vk_hash: [u8; 32]) {
+    let mut simulator = Simulator::new();
+    valid_proof.public_values[0] ^= 1;
+
+    assert!(matches!(
+        simulator.submit_proof(VkOrHash::Vk(valid_vk_hash), &valid_proof),
+        Err(SimulatorError::VerificationFailed(_))
+    ));
+    assert!(simulator.pending().is_empty());
+    assert!(simulator.events().is_empty());
+}
+
+#[test]
+fn reject_empty_aggregation() {
+    assert!(matches!(
+        Simulator::new().aggregate(),
+        Err(SimulatorError::EmptyAggregation)
+    ));
+}
+
+#[rstest]
+fn prove_inclusion_of_every_statement(#[values(1, 2, 3, 4, 5, 7, 8, 13)] leaves: u8) {
+    let statements = (0..leaves).map(|i| [i; 32]).collect::<Vec<_>>();
+    let aggregation = Aggregation::new(1, statements);
+
+    for i in 0..leaves as usize {
+        let inclusion = aggregation.proof(i).unwrap();
+        assert_eq!(inclusion.root, aggregation.root);
+        assert!(inclusion.is_valid());
+    }
+    assert!(aggregation.proof(leaves as usize).is_none());
+}
+
+#[rstest]
+fn compute_reference_statement(valid_proof: SP1ZkvProofWithPublicValues, valid_vk_hash: [u8; 32]) {
+    assert_eq!(
+        hex::encode(valid_proof.statement_hash(&valid_vk_hash)),
+        "904f415b0560295f89903c3f483ef4d5102335c638ebabd60e947ec5b41a884d"
+    );
+    assert_eq!(
+        hex::encode(valid_proof.statement_leaf(&valid_vk_hash)),
+        "e35372765810441004e86bf5f1ee78d2aa9cc585362c22b295d8803a413ab925"
+    );
+}
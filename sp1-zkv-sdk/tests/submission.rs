@@ -0,0 +1,212 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use common::{proof_bytes, valid_proof, valid_vk_hash};
+use rstest::{fixture, rstest};
+use sha3::{Digest, Keccak256};
+use sp1_zkv_sdk::{
+    SP1ZkvProofWithPublicValues, Submission, VkOrHash,
+    submission::{JsonPayload, JsonProofData},
+};
+
+#[fixture]
+fn small_submission() -> Submission {
+    Submission {
+        vk: VkOrHash::Vk([0x11; 32]),
+        proof: vec![1, 2, 3],
+        pubs: vec![0xaa, 0xbb],
+        domain_id: None,
+    }
+}
+
+#[rstest]
+fn encode_json_payload(small_submission: Submission) {
+    assert_eq!(
+        small_submission.to_json(),
+        concat!(
+            r#"{"proofType":"sp1","vkRegistered":false,"proofData":{"proof":"0x010203","#,
+            r#""publicSignals":"0xaabb","#,
+            r#""vk":"0x1111111111111111111111111111111111111111111111111111111111111111"}}"#
+        )
+    );
+}
+
+#[rstest]
+fn encode_json_payload_with_registered_vk_and_domain(small_submission: Submission) {
+    let submission = Submission {
+        vk: VkOrHash::Hash([0x22; 32]),
+        ..small_submission
+    }
+    .with_domain_id(7);
+
+    assert_eq!(
+        submission.to_json(),
+        concat!(
+            r#"{"proofType":"sp1","vkRegistered":true,"proofData":{"proof":"0x010203","#,
+            r#""publicSignals":"0xaabb","#,
+            r#""vk":"0x2222222222222222222222222222222222222222222222222222222222222222"},"#,
+            r#""domainId":7}"#
+        )
+    );
+}
+
+#[rstest]
+fn json_payload_round_trips(small_submission: Submission) {
+    let payload: JsonPayload =
+        serde_json::from_str(&small_submission.with_domain_id(7).to_json()).unwrap();
+
+    assert_eq!(
+        payload,
+        JsonPayload {
+            proof_type: "sp1".into(),
+            vk_registered: false,
+            proof_data: JsonProofData {
+                proof: "0x010203".into(),
+                public_signals: "0xaabb".into(),
+                vk: format!("0x{}", "11".repeat(32)),
+            },
+            domain_id: Some(7),
+        }
+    );
+}
+
+#[rstest]
+fn encode_call_args(small_submission: Submission) {
+    assert_eq!(
+        hex::encode(small_submission.encode_call_args()),
+        concat!(
+            "00",
+            "1111111111111111111111111111111111111111111111111111111111111111",
+            "0c010203",
+            "08aabb",
+            "00"
+        )
+    );
+}
+
+#[rstest]
+fn encode_call_args_with_registered_vk_and_domain(small_submission: Submission) {
+    let submission = Submission {
+        vk: VkOrHash::Hash([0x22; 32]),
+        ..small_submission
+    }
+    .with_domain_id(7);
+
+    assert_eq!(
+        hex::encode(submission.encode_call_args()),
+        concat!(
+            "01",
+            "2222222222222222222222222222222222222222222222222222222222222222",
+            "0c010203",
+            "08aabb",
+            "0107000000"
+        )
+    );
+}
+
+#[rstest]
+fn build_submission_from_zkv_proof(
+    valid_proof: SP1ZkvProofWithPublicValues,
+    valid_vk_hash: [u8; 32],
+) {
+    let submission = Submission::new(&valid_proof, VkOrHash::Vk(valid_vk_hash)).unwrap();
+
+    // The pallet decodes the proof as a `sp1_zkv_verifier::Proof`.
+    let (proof, read): (sp1_zkv_verifier::Proof, _) =
+        bincode::serde::decode_from_slice(&submission.proof, bincode::config::legacy()).unwrap();
+    assert_eq!(read, submission.proof.len());
+    assert!(sp1_zkv_verifier::verify(&valid_vk_hash, &proof, &submission.pubs).is_ok());
+
+    assert_eq!(submission.proof.len(), 670295);
+    assert_eq!(
+        hex::encode(&submission.pubs),
+        "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+    );
+    assert_eq!(
+        hex::encode(Keccak256::digest(submission.to_json())),
+        "65ed5965b7403a12a858fddd5ab81edd4317465fee304f06fc5f652001325b6f"
+    );
+}
+
+#[rstest]
+fn match_call_args_assembled_by_hand(
+    proof_bytes: Vec<u8>,
+    valid_proof: SP1ZkvProofWithPublicValues,
+    valid_vk_hash: [u8; 32],
+) {
+    // The proof file holds the proof followed by the public inputs, as a `u64` length and bytes.
+    let (proof, pubs) = proof_bytes.split_at(proof_bytes.len() - 40);
+    let pubs = &pubs[8..];
+    let expected = [
+        &[0x00][..],
+        &valid_vk_hash,
+        // Four bytes compact length mode: `(len << 2) | 0b10`, little endian.
+        &(((proof.len() as u32) << 2) | 0b10).to_le_bytes(),
+        proof,
+        // Single byte compact length mode: `len << 2`.
+        &[(pubs.len() as u8) << 2],
+        pubs,
+        &[0x00],
+    ]
+    .concat();
+
+    let call_args = Submission::new(&valid_proof, VkOrHash::Vk(valid_vk_hash))
+        .unwrap()
+        .encode_call_args();
+
+    assert_eq!(call_args, expected);
+}
+
+// The digests below were also recomputed with `openssl dgst -keccak-256` over the payloads
+// assembled by hand from the proof file, as in `match_call_args_assembled_by_hand`.
+#[rstest]
+fn lock_call_args_of_zkv_proof(valid_proof: SP1ZkvProofWithPublicValues, valid_vk_hash: [u8; 32]) {
+    let call_args = Submission::new(&valid_proof, VkOrHash::Vk(valid_vk_hash))
+        .unwrap()
+        .encode_call_args();
+
+    // Vk variant, vk digest and compact length of the proof.
+    assert_eq!(
+        hex::encode(&call_args[..37]),
+        "0045946758049372c74bceb6ba6526a9661b1915a3403125e470d5ff7f6c15c0dd5ee92800"
+    );
+    assert_eq!(
+        hex::encode(Keccak256::digest(&call_args)),
+        "e313fee94826f8b8824732bb37b3717bc3b2bc8d22a2fccc53ee1c41d0a23f65"
+    );
+}
+
+#[rstest]
+fn lock_call_args_of_zkv_proof_with_domain(
+    valid_proof: SP1ZkvProofWithPublicValues,
+    valid_vk_hash: [u8; 32],
+) {
+    let call_args = Submission::new(&valid_proof, VkOrHash::Vk(valid_vk_hash))
+        .unwrap()
+        .with_domain_id(7)
+        .encode_call_args();
+
+    // Compact length and public inputs, then the domain id.
+    assert_eq!(
+        hex::encode(&call_args[call_args.len() - 38..]),
+        "80b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde90107000000"
+    );
+    assert_eq!(
+        hex::encode(Keccak256::digest(&call_args)),
+        "bc9b6e29c7603bb8322e7c1c1f055768b24e2e14df2bfa8e97a3a7b0f5843263"
+    );
+}
@@ -0,0 +1,84 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verification of zkVerify aggregation receipts.
+//!
+//! An aggregation commits to its statements with a Keccak binary Merkle tree built like
+//! `binary_merkle_tree` does on chain: leaves are [`statement_leaf`]s, inner nodes hash the
+//! concatenation of their two children, and the last node of a level with an odd number of nodes
+//! is promoted to the next level unchanged. Inclusion paths hold no sibling for the levels where
+//! the node is promoted.
+
+use crate::statement::statement_leaf;
+use sha3::{Digest, Keccak256};
+
+/// Check that the statement of an SP1 proof for program `vkey_digest` with public `inputs` is
+/// the leaf at `leaf_index` of the aggregation with `number_of_leaves` statements and the given
+/// `root`, according to the inclusion `path`.
+pub fn is_statement_included(
+    vkey_digest: &[u8; 32],
+    inputs: &[u8],
+    path: &[[u8; 32]],
+    leaf_index: usize,
+    number_of_leaves: usize,
+    root: &[u8; 32],
+) -> bool {
+    aggregation_root(
+        statement_leaf(vkey_digest, inputs),
+        path,
+        leaf_index,
+        number_of_leaves,
+    )
+    .is_some_and(|computed| computed == *root)
+}
+
+/// Compute the root of the aggregation with `number_of_leaves` leaves in which `leaf` sits at
+/// `leaf_index` according to `path`. Return `None` if `leaf_index` is out of range or `path`
+/// doesn't have one sibling for each level where the node isn't promoted.
+pub fn aggregation_root(
+    leaf: [u8; 32],
+    path: &[[u8; 32]],
+    leaf_index: usize,
+    number_of_leaves: usize,
+) -> Option<[u8; 32]> {
+    if leaf_index >= number_of_leaves {
+        return None;
+    }
+
+    let mut siblings = path.iter();
+    let mut node = leaf;
+    let mut index = leaf_index;
+    let mut width = number_of_leaves;
+
+    while width > 1 {
+        if index ^ 1 < width {
+            let sibling = siblings.next()?;
+            let (left, right) = if index % 2 == 0 {
+                (&node, sibling)
+            } else {
+                (sibling, &node)
+            };
+            node = Keccak256::new()
+                .chain_update(left)
+                .chain_update(right)
+                .finalize()
+                .into();
+        }
+        index /= 2;
+        width = width.div_ceil(2);
+    }
+
+    siblings.next().is_none().then_some(node)
+}
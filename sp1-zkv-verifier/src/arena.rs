@@ -0,0 +1,174 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Error, HashBackend, MAX_VERIFY_HEAP, Proof, verify_with_backend};
+use alloc::boxed::Box;
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    mem::MaybeUninit,
+    ptr::{self, NonNull},
+    sync::atomic::{AtomicBool, Ordering},
+};
+use linked_list_allocator::LockedHeap;
+
+/// The arena keeps `1 / RESERVE_FRACTION` of its region as a reserve, to absorb allocations once
+/// the rest is full.
+const RESERVE_FRACTION: usize = 8;
+
+/// An allocator confined to a fixed memory region supplied by the caller.
+///
+/// Install it as the `#[global_allocator]` and hand it its region with [`Arena::init`] before the
+/// first allocation. Freed memory is reused, and running out of it makes allocations fail
+/// instead of growing.
+///
+/// A global allocator can't fail an allocation without aborting the process, so the arena keeps
+/// a reserve it only serves once the rest of its region is exhausted, and remembers that it had
+/// to. This is what lets [`verify_in_arena`] report an arena running out of memory in the middle
+/// of a verification as an error.
+pub struct Arena {
+    heap: LockedHeap,
+    reserve: LockedHeap,
+    overflowed: AtomicBool,
+}
+
+/// Error returned by [`verify_in_arena`].
+#[derive(Debug)]
+pub enum ArenaError {
+    /// The arena has less free memory than a verification may need, or ran out of it during the
+    /// verification.
+    Exhausted { required: usize, available: usize },
+    /// The arena isn't the global allocator, so it can't account for what the verification
+    /// allocates.
+    NotGlobalAllocator,
+    /// The proof is invalid.
+    Verification(Error),
+}
+
+impl Arena {
+    /// Create an arena without any memory: every allocation fails until [`Arena::init`].
+    pub const fn empty() -> Self {
+        Self {
+            heap: LockedHeap::empty(),
+            reserve: LockedHeap::empty(),
+            overflowed: AtomicBool::new(false),
+        }
+    }
+
+    /// Hand `region` to the arena, keeping the last eighth of it as a reserve. Must be called at
+    /// most once.
+    ///
+    /// # Panics
+    ///
+    /// If the reserve is too small to hold the bookkeeping of a single free block.
+    pub fn init(&self, region: &'static mut [MaybeUninit<u8>]) {
+        let split = region.len() - region.len() / RESERVE_FRACTION;
+        let (heap, reserve) = region.split_at_mut(split);
+        self.heap.lock().init_from_slice(heap);
+        self.reserve.lock().init_from_slice(reserve);
+    }
+
+    /// Size of the region managed by the arena, reserve excluded.
+    pub fn size(&self) -> usize {
+        self.heap.lock().size()
+    }
+
+    /// Bytes currently allocated from the arena, reserve included.
+    pub fn used(&self) -> usize {
+        self.heap.lock().used() + self.reserve.lock().used()
+    }
+
+    /// Bytes currently free in the arena, reserve excluded.
+    pub fn free(&self) -> usize {
+        self.heap.lock().free()
+    }
+
+    /// Return whether `ptr` was allocated from this arena.
+    pub fn contains(&self, ptr: *const u8) -> bool {
+        heap_contains(&self.heap, ptr) || heap_contains(&self.reserve, ptr)
+    }
+
+    /// Return whether an allocation was served from the reserve since the last call.
+    pub fn take_overflowed(&self) -> bool {
+        self.overflowed.swap(false, Ordering::SeqCst)
+    }
+}
+
+fn heap_contains(heap: &LockedHeap, ptr: *const u8) -> bool {
+    let heap = heap.lock();
+    heap.bottom() as *const u8 <= ptr && ptr < heap.top() as *const u8
+}
+
+unsafe impl GlobalAlloc for Arena {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if let Ok(ptr) = self.heap.lock().allocate_first_fit(layout) {
+            return ptr.as_ptr();
+        }
+        let ptr = self.reserve.lock().allocate_first_fit(layout);
+        if ptr.is_ok() {
+            self.overflowed.store(true, Ordering::SeqCst);
+        }
+        ptr.map_or(ptr::null_mut(), NonNull::as_ptr)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let Some(ptr) = NonNull::new(ptr) else {
+            return;
+        };
+        if heap_contains(&self.reserve, ptr.as_ptr()) {
+            unsafe { self.reserve.lock().deallocate(ptr, layout) };
+        } else {
+            unsafe { self.heap.lock().deallocate(ptr, layout) };
+        }
+    }
+}
+
+/// Verify a proof like [`verify_with_backend`] within the memory of `arena`, which must be the
+/// global allocator.
+///
+/// Checking the free memory up front turns an undersized arena into an error, rather than an
+/// allocation failure in the middle of the verification. Should the arena still run out during
+/// the verification, because something else allocated meanwhile, the verification completes on
+/// the arena's reserve and is reported as [`ArenaError::Exhausted`]. Only running out of the
+/// reserve too makes the allocation fail.
+pub fn verify_in_arena<H: HashBackend>(
+    arena: &Arena,
+    backend: &H,
+    vkey_digest: &[u8; 32],
+    proof: &Proof,
+    inputs: &[u8],
+) -> Result<(), ArenaError> {
+    let available = arena.free();
+    if available < MAX_VERIFY_HEAP {
+        return Err(ArenaError::Exhausted {
+            required: MAX_VERIFY_HEAP,
+            available,
+        });
+    }
+    let probe = Box::new(0u8);
+    if !arena.contains(&*probe) {
+        return Err(ArenaError::NotGlobalAllocator);
+    }
+    drop(probe);
+
+    arena.take_overflowed();
+    let result = verify_with_backend(backend, vkey_digest, proof, inputs);
+    if arena.take_overflowed() {
+        return Err(ArenaError::Exhausted {
+            required: MAX_VERIFY_HEAP,
+            available,
+        });
+    }
+    result.map_err(ArenaError::Verification)
+}
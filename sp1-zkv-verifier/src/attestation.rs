@@ -0,0 +1,112 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Signed attestations of verification results, for off-chain consumers that trust a verifier
+//! service instead of verifying proofs themselves.
+
+use crate::{
+    Error, Proof, hash::HashBackend, statement::VerifiedStatement, verifier::verify_statement,
+};
+use ed25519_dalek::{Signature, SignatureError, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+/// Domain separator of attestation signatures.
+const ATTESTATION_DOMAIN: &[u8] = b"sp1-zkv-attestation-v1";
+const MESSAGE_LEN: usize = ATTESTATION_DOMAIN.len() + 32 + 32 + 1 + 32 + 8 + 8;
+
+/// A [`VerifiedStatement`] signed with ed25519 by a verifier service.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attestation {
+    pub statement: VerifiedStatement,
+    /// When the proof was verified, in a unit chosen by the signer.
+    pub timestamp: u64,
+    /// Distinguishes attestations of the same statement issued at the same time.
+    pub nonce: u64,
+    pub signature: Signature,
+}
+
+impl Attestation {
+    /// Attest `statement` with `signing_key`.
+    pub fn sign(
+        statement: VerifiedStatement,
+        timestamp: u64,
+        nonce: u64,
+        signing_key: &SigningKey,
+    ) -> Self {
+        let signature = signing_key.sign(&signed_message(&statement, timestamp, nonce));
+        Self {
+            statement,
+            timestamp,
+            nonce,
+            signature,
+        }
+    }
+}
+
+/// Verify a proof like [`crate::verify_with_backend`] and, on success, attest what it establishes
+/// with `signing_key`.
+pub fn verify_and_attest<H: HashBackend>(
+    backend: &H,
+    signing_key: &SigningKey,
+    vkey_digest: &[u8; 32],
+    proof: &Proof,
+    inputs: &[u8],
+    timestamp: u64,
+    nonce: u64,
+) -> Result<Attestation, Error> {
+    let statement = verify_statement(backend, vkey_digest, proof, inputs)?;
+    Ok(Attestation::sign(statement, timestamp, nonce, signing_key))
+}
+
+/// Check that `attestation` was signed by `verifying_key`.
+pub fn verify_attestation(
+    attestation: &Attestation,
+    verifying_key: &VerifyingKey,
+) -> Result<(), SignatureError> {
+    let message = signed_message(
+        &attestation.statement,
+        attestation.timestamp,
+        attestation.nonce,
+    );
+    verifying_key.verify_strict(&message, &attestation.signature)
+}
+
+/// Encode the signed fields with a fixed layout, so that signatures don't depend on a
+/// serialization format.
+fn signed_message(statement: &VerifiedStatement, timestamp: u64, nonce: u64) -> [u8; MESSAGE_LEN] {
+    let VerifiedStatement {
+        vkey_digest,
+        committed_value_digest,
+        hash_kind,
+        vk_root,
+    } = statement;
+
+    let mut message = [0; MESSAGE_LEN];
+    let fields: [&[u8]; 7] = [
+        ATTESTATION_DOMAIN,
+        vkey_digest,
+        committed_value_digest,
+        &[*hash_kind as u8],
+        vk_root,
+        &timestamp.to_le_bytes(),
+        &nonce.to_le_bytes(),
+    ];
+    let mut offset = 0;
+    for field in fields {
+        message[offset..offset + field.len()].copy_from_slice(field);
+        offset += field.len();
+    }
+    message
+}
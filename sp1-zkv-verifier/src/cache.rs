@@ -0,0 +1,114 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::RecursionVkHash;
+use alloc::{collections::VecDeque, vec::Vec};
+
+/// Bounded cache of recursion verifying key hashes that are known to be members of the
+/// recursion vk root.
+///
+/// Hashes land in the cache either because their Merkle membership was proven during a
+/// verification, or because the caller registered them as trusted. Proven hashes are evicted in
+/// insertion order once `capacity` is reached; trusted hashes are never evicted.
+#[derive(Debug, Clone)]
+pub struct RecursionVkCache {
+    capacity: usize,
+    proven: VecDeque<RecursionVkHash>,
+    trusted: Vec<RecursionVkHash>,
+}
+
+impl RecursionVkCache {
+    /// Create an empty cache remembering at most `capacity` proven recursion vk hashes.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            proven: VecDeque::with_capacity(capacity),
+            trusted: Vec::new(),
+        }
+    }
+
+    /// Create a cache whose allowlist is pre-populated with `trusted` recursion vk hashes.
+    pub fn with_trusted(
+        capacity: usize,
+        trusted: impl IntoIterator<Item = RecursionVkHash>,
+    ) -> Self {
+        let mut cache = Self::new(capacity);
+        trusted.into_iter().for_each(|vk_hash| cache.trust(vk_hash));
+        cache
+    }
+
+    /// Register `vk_hash` as a trusted member of the recursion vk root. The caller is responsible
+    /// for making sure it actually is one: verifications using it skip the membership check.
+    pub fn trust(&mut self, vk_hash: RecursionVkHash) {
+        if !self.trusted.contains(&vk_hash) {
+            self.trusted.push(vk_hash);
+        }
+    }
+
+    /// Return whether `vk_hash` is either trusted or already proven to be a member.
+    pub fn contains(&self, vk_hash: &RecursionVkHash) -> bool {
+        self.trusted.contains(vk_hash) || self.proven.contains(vk_hash)
+    }
+
+    /// Number of proven (non-trusted) hashes currently cached.
+    pub fn len(&self) -> usize {
+        self.proven.len()
+    }
+
+    /// Return whether no proven hashes are currently cached.
+    pub fn is_empty(&self) -> bool {
+        self.proven.is_empty()
+    }
+
+    /// Remember `vk_hash` as proven, evicting the oldest proven hash if the cache is full.
+    pub(crate) fn insert(&mut self, vk_hash: RecursionVkHash) {
+        if self.capacity == 0 || self.contains(&vk_hash) {
+            return;
+        }
+        if self.proven.len() == self.capacity {
+            self.proven.pop_front();
+        }
+        self.proven.push_back(vk_hash);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p3_baby_bear::BabyBear;
+    use p3_field::AbstractField;
+
+    fn vk_hash(i: u32) -> RecursionVkHash {
+        [BabyBear::from_canonical_u32(i); 8]
+    }
+
+    #[test]
+    fn evicts_oldest_proven_hash_when_full() {
+        let mut cache = RecursionVkCache::new(2);
+        (0..3).for_each(|i| cache.insert(vk_hash(i)));
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.contains(&vk_hash(0)));
+        assert!(cache.contains(&vk_hash(1)));
+        assert!(cache.contains(&vk_hash(2)));
+    }
+
+    #[test]
+    fn does_not_cache_anything_with_zero_capacity() {
+        let mut cache = RecursionVkCache::new(0);
+        cache.insert(vk_hash(0));
+        assert!(cache.is_empty());
+        assert!(!cache.contains(&vk_hash(0)));
+    }
+}
@@ -0,0 +1,153 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::hash::{HashBackend, POSEIDON2_WIDTH};
+use p3_baby_bear::BabyBear;
+use p3_field::{AbstractExtensionField, AbstractField, PrimeField64};
+use sp1_stark_no_std::DIGEST_SIZE;
+
+/// Rate of the duplex sponge.
+const RATE: usize = 8;
+
+/// The Fiat-Shamir transcript of the shrink STARK, permuting with a [`HashBackend`].
+///
+/// Behaves exactly like Plonky3's `DuplexChallenger` over BabyBear with width 16 and rate 8, which
+/// the shrink proofs are made with, but keeps its buffers inline.
+pub(crate) struct Challenger<'a, H> {
+    backend: &'a H,
+    sponge_state: [BabyBear; POSEIDON2_WIDTH],
+    input_buffer: [BabyBear; RATE],
+    input_len: usize,
+    output_buffer: [BabyBear; RATE],
+    output_len: usize,
+}
+
+impl<'a, H: HashBackend> Challenger<'a, H> {
+    pub(crate) fn new(backend: &'a H) -> Self {
+        Self {
+            backend,
+            sponge_state: [BabyBear::zero(); POSEIDON2_WIDTH],
+            input_buffer: [BabyBear::zero(); RATE],
+            input_len: 0,
+            output_buffer: [BabyBear::zero(); RATE],
+            output_len: 0,
+        }
+    }
+
+    /// Overwrite the start of the state with the buffered inputs and permute it, refilling the
+    /// outputs.
+    fn duplexing(&mut self) {
+        self.sponge_state[..self.input_len].copy_from_slice(&self.input_buffer[..self.input_len]);
+        self.input_len = 0;
+        self.backend.poseidon2_permute(&mut self.sponge_state);
+        self.output_buffer
+            .copy_from_slice(&self.sponge_state[..RATE]);
+        self.output_len = RATE;
+    }
+
+    pub(crate) fn observe(&mut self, value: BabyBear) {
+        // Any output left over would not depend on `value`.
+        self.output_len = 0;
+        self.input_buffer[self.input_len] = value;
+        self.input_len += 1;
+        if self.input_len == RATE {
+            self.duplexing();
+        }
+    }
+
+    pub(crate) fn observe_slice(&mut self, values: &[BabyBear]) {
+        values.iter().for_each(|value| self.observe(*value));
+    }
+
+    pub(crate) fn observe_digest(&mut self, digest: &[BabyBear; DIGEST_SIZE]) {
+        self.observe_slice(digest);
+    }
+
+    pub(crate) fn observe_ext<EF: AbstractExtensionField<BabyBear>>(&mut self, value: EF) {
+        self.observe_slice(value.as_base_slice());
+    }
+
+    pub(crate) fn sample(&mut self) -> BabyBear {
+        // Challenges must reflect every input, and outputs are consumed from the end.
+        if self.input_len != 0 || self.output_len == 0 {
+            self.duplexing();
+        }
+        self.output_len -= 1;
+        self.output_buffer[self.output_len]
+    }
+
+    pub(crate) fn sample_ext<EF: AbstractExtensionField<BabyBear>>(&mut self) -> EF {
+        EF::from_base_fn(|_| self.sample())
+    }
+
+    /// Sample a random integer of `bits` bits, `bits` being below the bit size of BabyBear.
+    pub(crate) fn sample_bits(&mut self, bits: usize) -> usize {
+        let value = self.sample().as_canonical_u64() as usize;
+        value & ((1 << bits) - 1)
+    }
+
+    /// Check that observing `witness` makes the next `bits` sampled bits zero.
+    pub(crate) fn check_witness(&mut self, bits: usize, witness: BabyBear) -> bool {
+        self.observe(witness);
+        self.sample_bits(bits) == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InnerSC, hash::NativeBackend};
+    use p3_challenger::{
+        CanObserve, CanSample, CanSampleBits, FieldChallenger, GrindingChallenger,
+    };
+    use sp1_stark_no_std::StarkGenericConfig;
+
+    type Challenge = <InnerSC as StarkGenericConfig>::Challenge;
+
+    #[test]
+    fn behave_like_the_upstream_duplex_challenger() {
+        let backend = NativeBackend::default();
+        let mut challenger = Challenger::new(&backend);
+        let mut upstream = InnerSC::compressed().challenger();
+
+        // Observe and sample runs of every length around the rate.
+        let mut next = 0u32;
+        for round in 0..40 {
+            for _ in 0..round % 11 {
+                let value = BabyBear::from_canonical_u32(next);
+                next += 1;
+                challenger.observe(value);
+                upstream.observe(value);
+            }
+            match round % 4 {
+                0 => assert_eq!(
+                    challenger.sample(),
+                    CanSample::<BabyBear>::sample(&mut upstream)
+                ),
+                1 => assert_eq!(
+                    challenger.sample_ext::<Challenge>(),
+                    upstream.sample_ext_element::<Challenge>()
+                ),
+                2 => assert_eq!(challenger.sample_bits(10), upstream.sample_bits(10)),
+                _ => {
+                    let witness = BabyBear::from_canonical_u32(round);
+                    assert_eq!(
+                        challenger.check_witness(1, witness),
+                        upstream.check_witness(1, witness)
+                    );
+                }
+            }
+        }
+    }
+}
@@ -13,22 +13,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::InnerSC;
-use alloc::vec::Vec;
+use crate::{
+    hash::{HashBackend, poseidon2_hash},
+    statement::HashKind,
+};
 use itertools::Itertools;
 use p3_baby_bear::BabyBear;
 use p3_field::{AbstractField, PrimeField32};
-use p3_symmetric::CryptographicHasher;
-use sha2::{Digest, Sha256};
 use sp1_recursion_core_no_std::air::{NUM_PV_ELMS_TO_HASH, RecursionPublicValues};
-use sp1_stark_no_std::InnerHash;
 
 /// Check if the digest of the public values is correct.
-pub fn is_recursion_public_values_valid(
-    config: &InnerSC,
+pub fn is_recursion_public_values_valid<H: HashBackend>(
+    backend: &H,
     public_values: &RecursionPublicValues<BabyBear>,
 ) -> bool {
-    let expected_digest = recursion_public_values_digest(config, public_values);
+    let expected_digest = recursion_public_values_digest(backend, public_values);
     for (value, expected) in public_values.digest.iter().copied().zip_eq(expected_digest) {
         if value != expected {
             return false;
@@ -38,44 +37,56 @@ pub fn is_recursion_public_values_valid(
 }
 
 /// Compute the digest of the public values.
-pub fn recursion_public_values_digest(
-    config: &InnerSC,
+pub fn recursion_public_values_digest<H: HashBackend>(
+    backend: &H,
     public_values: &RecursionPublicValues<BabyBear>,
 ) -> [BabyBear; 8] {
-    let hash = InnerHash::new(config.perm.clone());
     let pv_array = public_values.as_array();
-    hash.hash_slice(&pv_array[0..NUM_PV_ELMS_TO_HASH])
+    poseidon2_hash(backend, pv_array[0..NUM_PV_ELMS_TO_HASH].iter().copied())
 }
 
-pub fn is_public_inputs_digest_valid(
+/// Find which hash of the public inputs the proof commits to, if any.
+pub fn public_inputs_hash_kind<H: HashBackend>(
+    backend: &H,
     recursion_public_values: &RecursionPublicValues<BabyBear>,
     public_inputs: &[u8],
-) -> bool {
-    let committed_value_digest_bytes: [u8; 32] = recursion_public_values
-        .committed_value_digest
-        .iter()
-        .flat_map(|w| w.0.iter().map(|x| x.as_canonical_u32() as u8))
-        .collect_vec()
-        .try_into()
-        .unwrap();
+) -> Option<HashKind> {
+    let committed_value_digest_bytes = committed_value_digest_bytes(recursion_public_values);
+
+    let blake3_input_hash = backend.blake3(public_inputs);
+    let sha256_input_hash = backend.sha256(public_inputs);
 
-    let blake3_input_hash = blake3::hash(public_inputs);
-    let sha256_input_hash = Sha256::digest(public_inputs);
+    if sha256_input_hash == committed_value_digest_bytes {
+        Some(HashKind::Sha256)
+    } else if blake3_input_hash == committed_value_digest_bytes {
+        Some(HashKind::Blake3)
+    } else {
+        None
+    }
+}
+
+/// The digest of the public inputs committed by the program.
+pub fn committed_value_digest_bytes(
+    recursion_public_values: &RecursionPublicValues<BabyBear>,
+) -> [u8; 32] {
+    let committed_value_digest = &recursion_public_values.committed_value_digest;
+    core::array::from_fn(|i| committed_value_digest[i / 4].0[i % 4].as_canonical_u32() as u8)
+}
 
-    sha256_input_hash == committed_value_digest_bytes.into()
-        || blake3_input_hash == committed_value_digest_bytes
+/// Pack a digest of 8 BabyBear elements into big-endian bytes, the layout of SP1 vk digests.
+pub fn digest_bytes(digest: &[BabyBear; 8]) -> [u8; 32] {
+    core::array::from_fn(|i| digest[i / 4].as_canonical_u32().to_be_bytes()[i % 4])
 }
 
 pub fn is_vkey_digest_valid(
     recursion_public_values: &RecursionPublicValues<BabyBear>,
     vkey_digest: &[u8; 32],
 ) -> bool {
-    let vkey_hash: [BabyBear; 8] = vkey_digest
-        .chunks_exact(4)
-        .map(|bytes| BabyBear::from_canonical_u32(u32::from_be_bytes(bytes.try_into().unwrap())))
-        .collect::<Vec<_>>()
-        .try_into()
-        .unwrap();
+    let vkey_hash: [BabyBear; 8] = core::array::from_fn(|i| {
+        BabyBear::from_canonical_u32(u32::from_be_bytes(
+            vkey_digest[4 * i..4 * i + 4].try_into().unwrap(),
+        ))
+    });
 
     recursion_public_values.sp1_vk_digest == vkey_hash
 }
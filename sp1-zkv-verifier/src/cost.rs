@@ -0,0 +1,669 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    Error, InnerSC, Proof,
+    fri::fri_params,
+    verifier::{ShrinkChip, hash_babybear_input_len, shrink_machine},
+};
+use alloc::{collections::BTreeSet, rc::Rc, vec, vec::Vec};
+use core::ops::{Add, AddAssign};
+use p3_air::BaseAir;
+use p3_baby_bear::BabyBear;
+use p3_uni_stark::{SymbolicExpression, get_symbolic_constraints};
+use sp1_recursion_core_no_std::air::NUM_PV_ELMS_TO_HASH;
+use sp1_stark_no_std::{DIGEST_SIZE, MachineVerificationError, StarkVerifyingKey, air::MachineAir};
+use spin::Once;
+
+/// Rate of the Poseidon2 sponge used for every field-element hash in the verifier.
+const POSEIDON2_RATE: usize = 8;
+/// Degree of the extension field used by the shrink STARK.
+const EXTENSION_DEGREE: usize = 4;
+/// Number of committed trace batches: preprocessed, main, permutation and quotient.
+const NUM_TRACE_BATCHES: usize = 4;
+/// Points each batch is opened at: the out-of-domain point and the next row, except for the
+/// quotient which is only opened at the out-of-domain point.
+const OPENING_POINTS: [usize; NUM_TRACE_BATCHES] = [2, 2, 2, 1];
+/// Field elements observed for a vk: commitment, pc start, initial global cumulative sum and one
+/// element of padding.
+const VK_OBSERVED_LEN: usize = DIGEST_SIZE + 1 + 14 + 1;
+/// Field elements observed per chip: local and global cumulative sums.
+const CHIP_OBSERVED_LEN: usize = EXTENSION_DEGREE + 14;
+/// The constraint folder multiplies its accumulator by alpha and adds each constraint to it.
+const FOLD_OPS_PER_CONSTRAINT: usize = 2;
+/// Besides raising the out-of-domain point to the trace height, one squaring per bit, computing
+/// the selectors of a domain takes a few divisions and subtractions.
+const SELECTOR_OPS: usize = 8;
+/// Each interaction value is evaluated from its column (a multiplication and an addition), then
+/// mixed into the fingerprint with a power of beta (another multiplication and addition).
+const INTERACTION_OPS_PER_VALUE: usize = 4;
+/// Each interaction fingerprint starts from alpha plus its argument index, is inverted, weighted
+/// by the multiplicity and added to the LogUp sum.
+const INTERACTION_OPS: usize = 5;
+/// The PCS inverts `x - z` once per matrix and point (2 ops).
+const PCS_OPS_PER_POINT: usize = 2;
+/// Then it reduces each opened column with `(p(x) - p(z)) * (x - z)^-1` (2 ops), scales it by the
+/// running power of alpha and adds it to the reduced opening (2 ops), and steps the power of alpha
+/// (1 op).
+const PCS_OPS_PER_OPENING: usize = 5;
+/// Each FRI round adds the reduced opening of its height (1 op), places the query point among its
+/// sibling (1 op), interpolates the folded value `e0 + (beta - x0) * (e1 - e0) * (x1 - x0)^-1`
+/// (7 ops) and squares the query point (1 op).
+const FRI_OPS_PER_ROUND: usize = 10;
+/// Bytes absorbed by one SHA-256 block, and bytes appended by its padding.
+const SHA256_BLOCK_LEN: usize = 64;
+const SHA256_PADDING_LEN: usize = 9;
+/// Bytes absorbed by one BLAKE3 chunk.
+const BLAKE3_CHUNK_LEN: usize = 1024;
+
+/// Amount of work needed to verify a proof, counted in expensive primitives.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VerificationCost {
+    /// Poseidon2 permutations over BabyBear.
+    pub poseidon2_permutations: u64,
+    /// Arithmetic operations in BabyBear or its extension field.
+    pub field_ops: u64,
+    /// SHA-256 compression function invocations.
+    pub sha256_blocks: u64,
+    /// BLAKE3 chunks hashed.
+    pub blake3_chunks: u64,
+}
+
+impl VerificationCost {
+    /// Return whether every counter of `self` is at most the matching counter of `budget`.
+    pub fn fits_within(&self, budget: &VerificationCost) -> bool {
+        self.poseidon2_permutations <= budget.poseidon2_permutations
+            && self.field_ops <= budget.field_ops
+            && self.sha256_blocks <= budget.sha256_blocks
+            && self.blake3_chunks <= budget.blake3_chunks
+    }
+}
+
+impl Add for VerificationCost {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            poseidon2_permutations: self.poseidon2_permutations + rhs.poseidon2_permutations,
+            field_ops: self.field_ops + rhs.field_ops,
+            sha256_blocks: self.sha256_blocks + rhs.sha256_blocks,
+            blake3_chunks: self.blake3_chunks + rhs.blake3_chunks,
+        }
+    }
+}
+
+impl AddAssign for VerificationCost {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+/// Estimate the cost of verifying `proof` with public inputs of `inputs_len` bytes.
+///
+/// The estimate only depends on the recursion vk (chips and trace heights), the shrink machine
+/// (trace widths and constraints), the compressed FRI configuration and the lengths of the inputs
+/// and the vk Merkle path. None of the shard proof contents are trusted, so a proof can't make
+/// itself look cheaper than it is.
+///
+/// Fails with [`MachineVerificationError::InvalidVerificationKey`] when the recursion vk has a
+/// chip the shrink machine doesn't, as the verification would.
+pub fn estimate_cost(proof: &Proof, inputs_len: usize) -> Result<VerificationCost, Error> {
+    Ok(public_inputs_cost(inputs_len)
+        + public_values_digest_cost()
+        + recursion_vk_hash_cost(hash_babybear_input_len(&proof.vk))
+        + merkle_path_cost(proof.vk_merkle_proof.path.len())
+        + shrink_proof_cost(&proof.vk)?)
+}
+
+/// Cost of hashing the public inputs with both SHA-256 and BLAKE3.
+pub(crate) fn public_inputs_cost(inputs_len: usize) -> VerificationCost {
+    sha256_cost(inputs_len) + blake3_cost(inputs_len)
+}
+
+/// Cost of a SHA-256 digest of `len` bytes.
+pub(crate) fn sha256_cost(len: usize) -> VerificationCost {
+    VerificationCost {
+        sha256_blocks: (len + SHA256_PADDING_LEN).div_ceil(SHA256_BLOCK_LEN) as u64,
+        ..Default::default()
+    }
+}
+
+/// Cost of a BLAKE3 digest of `len` bytes.
+pub(crate) fn blake3_cost(len: usize) -> VerificationCost {
+    VerificationCost {
+        blake3_chunks: len.div_ceil(BLAKE3_CHUNK_LEN).max(1) as u64,
+        ..Default::default()
+    }
+}
+
+/// Cost of recomputing the recursion public values digest.
+pub(crate) fn public_values_digest_cost() -> VerificationCost {
+    sponge_cost(NUM_PV_ELMS_TO_HASH)
+}
+
+/// Cost of hashing a recursion vk made of `input_len` field elements.
+pub(crate) fn recursion_vk_hash_cost(input_len: usize) -> VerificationCost {
+    sponge_cost(input_len)
+}
+
+/// Cost of walking a recursion vk Merkle path of `path_len` siblings.
+pub(crate) fn merkle_path_cost(path_len: usize) -> VerificationCost {
+    VerificationCost {
+        poseidon2_permutations: path_len as u64,
+        ..Default::default()
+    }
+}
+
+/// Cost of verifying a shrink STARK proof against `vk`: constraints, PCS openings and FRI.
+pub(crate) fn shrink_proof_cost(
+    vk: &StarkVerifyingKey<InnerSC>,
+) -> Result<VerificationCost, Error> {
+    let shape = ProofShape::new(vk)?;
+    Ok(shape.constraints_cost() + shape.pcs_cost() + shape.fri_cost())
+}
+
+fn sponge_cost(input_len: usize) -> VerificationCost {
+    VerificationCost {
+        poseidon2_permutations: input_len.div_ceil(POSEIDON2_RATE) as u64,
+        ..Default::default()
+    }
+}
+
+/// Per-chip trace shape and constraint work, as seen by the shrink STARK verifier.
+struct ChipShape {
+    log_height: usize,
+    /// Base field columns of each committed batch.
+    widths: [usize; NUM_TRACE_BATCHES],
+    /// Committed matrices of each batch: the quotient is split in several chunks.
+    matrices: [usize; NUM_TRACE_BATCHES],
+    constraint_ops: usize,
+}
+
+/// The parts of the shrink STARK verification that determine its cost.
+pub(crate) struct ProofShape {
+    chips: Vec<ChipShape>,
+    log_blowup: usize,
+    num_queries: usize,
+    num_public_values: usize,
+}
+
+impl ProofShape {
+    /// The shape of every shrink proof verified against `vk`.
+    ///
+    /// The shrink machine only has preprocessed chips, so the vk fixes the height of every trace,
+    /// while the machine fixes their widths and constraints and the compressed FRI configuration
+    /// fixes the blowup and the number of queries.
+    pub(crate) fn new(vk: &StarkVerifyingKey<InnerSC>) -> Result<Self, Error> {
+        let machine = shrink_machine();
+        let chips = vk
+            .chip_information
+            .iter()
+            .map(|(name, domain, dimensions)| {
+                let (chip, constraint_ops) = machine
+                    .chips()
+                    .iter()
+                    .zip(chip_constraint_ops())
+                    .find(|(chip, _)| chip.name() == *name)
+                    .ok_or(MachineVerificationError::InvalidVerificationKey)?;
+                let quotient_chunks = chip.quotient_width();
+                Ok(ChipShape {
+                    log_height: domain.log_n,
+                    widths: [
+                        dimensions.width,
+                        chip.width(),
+                        EXTENSION_DEGREE * chip.permutation_width(),
+                        EXTENSION_DEGREE * quotient_chunks,
+                    ],
+                    matrices: [1, 1, 1, quotient_chunks],
+                    constraint_ops: *constraint_ops,
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+        let fri_params = fri_params();
+        Ok(Self {
+            chips,
+            log_blowup: fri_params.log_blowup,
+            num_queries: fri_params.num_queries,
+            num_public_values: machine.num_pv_elts(),
+        })
+    }
+
+    /// The final FRI polynomial is a constant, so there is one folding round per bit of the
+    /// tallest trace.
+    fn num_fri_rounds(&self) -> usize {
+        self.chips
+            .iter()
+            .map(|chip| chip.log_height)
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn log_max_lde_height(&self) -> usize {
+        self.num_fri_rounds() + self.log_blowup
+    }
+
+    /// Evaluating the constraints of every chip at the out-of-domain point.
+    pub(crate) fn constraints_cost(&self) -> VerificationCost {
+        VerificationCost {
+            field_ops: self
+                .chips
+                .iter()
+                .map(|chip| chip.constraint_ops + chip.log_height + SELECTOR_OPS)
+                .sum::<usize>() as u64,
+            ..Default::default()
+        }
+    }
+
+    /// Transcript hashing plus checking the trace openings of every query.
+    pub(crate) fn pcs_cost(&self) -> VerificationCost {
+        let per_query_ops: usize = self
+            .chips
+            .iter()
+            .map(|chip| {
+                let log_lde_height = chip.log_height + self.log_blowup;
+                (0..NUM_TRACE_BATCHES)
+                    .map(|batch| {
+                        // Computing the query point of each matrix is an exponentiation and a
+                        // multiplication by the coset shift.
+                        let per_matrix =
+                            2 * log_lde_height + 1 + OPENING_POINTS[batch] * PCS_OPS_PER_POINT;
+                        chip.matrices[batch] * per_matrix
+                            + OPENING_POINTS[batch] * chip.widths[batch] * PCS_OPS_PER_OPENING
+                    })
+                    .sum::<usize>()
+            })
+            .sum();
+
+        VerificationCost {
+            poseidon2_permutations: (self.transcript_permutations().0
+                + self.num_queries * self.query_batch_opening_permutations())
+                as u64,
+            field_ops: (self.num_queries * per_query_ops) as u64,
+            ..Default::default()
+        }
+    }
+
+    /// Sampling the FRI challenges and checking the commit phase openings of every query.
+    pub(crate) fn fri_cost(&self) -> VerificationCost {
+        let rounds = self.num_fri_rounds();
+        let log_max_lde_height = self.log_max_lde_height();
+        VerificationCost {
+            poseidon2_permutations: (self.transcript_permutations().1
+                + self.num_queries * self.query_fri_opening_permutations())
+                as u64,
+            // Computing the initial query point is an exponentiation.
+            field_ops: (self.num_queries * (2 * log_max_lde_height + rounds * FRI_OPS_PER_ROUND))
+                as u64,
+            ..Default::default()
+        }
+    }
+
+    /// Permutations of the transcript, before the opening argument and then for FRI, following
+    /// what the verifier observes and samples.
+    fn transcript_permutations(&self) -> (usize, usize) {
+        let mut duplex = DuplexCount::default();
+        duplex.observe(VK_OBSERVED_LEN);
+        duplex.observe(DIGEST_SIZE + self.num_public_values);
+        // The two permutation challenges, then the permutation commitment and cumulative sums.
+        duplex.sample(2 * EXTENSION_DEGREE);
+        duplex.observe(DIGEST_SIZE + self.chips.len() * CHIP_OBSERVED_LEN);
+        // Alpha, the quotient commitment, zeta and the alpha of the PCS.
+        duplex.sample(EXTENSION_DEGREE);
+        duplex.observe(DIGEST_SIZE);
+        duplex.sample(EXTENSION_DEGREE);
+        duplex.sample(EXTENSION_DEGREE);
+        let shard = duplex.take();
+
+        for _ in 0..self.num_fri_rounds() {
+            duplex.observe(DIGEST_SIZE);
+            duplex.sample(EXTENSION_DEGREE);
+        }
+        // The final polynomial, the proof of work witness and the query indices.
+        duplex.observe(EXTENSION_DEGREE + 1);
+        duplex.sample(1 + self.num_queries);
+        (shard, duplex.take())
+    }
+
+    /// Permutations needed to open one query in the four trace batches.
+    pub(crate) fn query_batch_opening_permutations(&self) -> usize {
+        (0..NUM_TRACE_BATCHES)
+            .map(|batch| self.batch_opening_permutations(batch))
+            .sum()
+    }
+
+    /// Permutations needed to open one query in every FRI commit phase round: hashing the folded
+    /// pair, then walking a path that gets one level shorter every round.
+    pub(crate) fn query_fri_opening_permutations(&self) -> usize {
+        let log_max_lde_height = self.log_max_lde_height();
+        (0..self.num_fri_rounds())
+            .map(|round| 1 + log_max_lde_height - 1 - round)
+            .sum()
+    }
+
+    /// Permutations needed to open one row of a batch commitment: hashing the rows of each
+    /// distinct height, compressing along the tallest path and injecting the shorter rows.
+    fn batch_opening_permutations(&self, batch: usize) -> usize {
+        let mut heights: Vec<(usize, usize)> = Vec::new();
+        for chip in &self.chips {
+            let log_lde_height = chip.log_height + self.log_blowup;
+            match heights.iter_mut().find(|(h, _)| *h == log_lde_height) {
+                Some((_, width)) => *width += chip.widths[batch],
+                None => heights.push((log_lde_height, chip.widths[batch])),
+            }
+        }
+        let Some(max_height) = heights.iter().map(|(h, _)| *h).max() else {
+            return 0;
+        };
+        let leaves: usize = heights
+            .iter()
+            .map(|(_, width)| width.div_ceil(POSEIDON2_RATE))
+            .sum();
+        max_height + leaves + (heights.len() - 1)
+    }
+}
+
+/// Operations spent on the constraints of each chip of the shrink machine, in the order of the
+/// machine, counted on first use since that takes building their symbolic form.
+fn chip_constraint_ops() -> &'static [usize] {
+    static CHIP_CONSTRAINT_OPS: Once<Vec<usize>> = Once::new();
+    CHIP_CONSTRAINT_OPS.call_once(|| {
+        let machine = shrink_machine();
+        machine
+            .chips()
+            .iter()
+            .map(|chip| constraint_ops(chip, machine.num_pv_elts()))
+            .collect()
+    })
+}
+
+/// Field operations spent checking the constraints of `chip`, a chip of the shrink machine with a
+/// trace of height `2^log_height`, at the out-of-domain point.
+pub(crate) fn chip_constraints_field_ops(chip: &ShrinkChip, log_height: usize) -> u64 {
+    let constraint_ops = shrink_machine()
+        .chips()
+        .iter()
+        .zip(chip_constraint_ops())
+        .find_map(|(machine_chip, ops)| core::ptr::eq(machine_chip, chip).then_some(*ops))
+        .unwrap_or_default();
+    (constraint_ops + log_height + SELECTOR_OPS) as u64
+}
+
+/// Counts the permutations of the transcript, which duplexes once its input buffer holds a full
+/// rate of elements, or when sampling after observing anything or once its outputs run out.
+#[derive(Default)]
+struct DuplexCount {
+    input_len: usize,
+    output_len: usize,
+    permutations: usize,
+}
+
+impl DuplexCount {
+    fn observe(&mut self, len: usize) {
+        for _ in 0..len {
+            self.output_len = 0;
+            self.input_len += 1;
+            if self.input_len == POSEIDON2_RATE {
+                self.duplex();
+            }
+        }
+    }
+
+    fn sample(&mut self, len: usize) {
+        for _ in 0..len {
+            if self.input_len != 0 || self.output_len == 0 {
+                self.duplex();
+            }
+            self.output_len -= 1;
+        }
+    }
+
+    fn duplex(&mut self) {
+        self.input_len = 0;
+        self.output_len = POSEIDON2_RATE;
+        self.permutations += 1;
+    }
+
+    /// The permutations counted since the last call.
+    fn take(&mut self) -> usize {
+        core::mem::take(&mut self.permutations)
+    }
+}
+
+/// Operations spent checking the constraints of `chip` at the out-of-domain point: its AIR
+/// constraints counted from their symbolic form, folding them together and its interactions.
+fn constraint_ops(chip: &ShrinkChip, num_public_values: usize) -> usize {
+    let constraints = get_symbolic_constraints(chip, chip.preprocessed_width(), num_public_values);
+    let mut seen = BTreeSet::new();
+    let air_ops: usize = constraints
+        .iter()
+        .map(|constraint| symbolic_ops(constraint, &mut seen))
+        .sum();
+    let interaction_ops: usize = chip
+        .sends()
+        .iter()
+        .chain(chip.receives())
+        .map(|interaction| INTERACTION_OPS + INTERACTION_OPS_PER_VALUE * interaction.values.len())
+        .sum();
+    air_ops + FOLD_OPS_PER_CONSTRAINT * constraints.len() + interaction_ops
+}
+
+/// Operations the verifier runs to evaluate `constraint`, skipping those already counted in
+/// `seen`.
+///
+/// Cloning a symbolic expression shares its operands, so the verifier computes it once however
+/// many times the AIR reuses it. An operation is therefore identified by its kind and the
+/// addresses of its operands.
+fn symbolic_ops(
+    constraint: &SymbolicExpression<BabyBear>,
+    seen: &mut BTreeSet<(u8, usize, usize)>,
+) -> usize {
+    let mut ops = 0;
+    let mut pending = vec![constraint];
+    while let Some(expression) = pending.pop() {
+        let (kind, x, y) = match expression {
+            SymbolicExpression::Add { x, y, .. } => (0, x, Some(y)),
+            SymbolicExpression::Sub { x, y, .. } => (1, x, Some(y)),
+            SymbolicExpression::Mul { x, y, .. } => (2, x, Some(y)),
+            SymbolicExpression::Neg { x, .. } => (3, x, None),
+            _ => continue,
+        };
+        let key = (
+            kind,
+            Rc::as_ptr(x) as usize,
+            y.map_or(0, |y| Rc::as_ptr(y) as usize),
+        );
+        if seen.insert(key) {
+            ops += 1;
+            pending.push(x);
+            pending.extend(y.map(|y| &**y));
+        }
+    }
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        hash::{CountingBackend, NativeBackend},
+        meter::{Meter, Stage, StageMetrics},
+        verifier::verify_shrink_proof,
+    };
+    use p3_field::AbstractField;
+    use std::fs::File;
+
+    fn valid_proof() -> Proof {
+        bincode::serde::decode_from_std_read(
+            &mut File::open("resources/proof.bin").unwrap(),
+            bincode::config::legacy(),
+        )
+        .unwrap()
+    }
+
+    /// Poseidon2 permutations spent checking the Merkle openings actually carried by `proof`:
+    /// one per path sibling, one per leaf chunk of 8 elements of each distinct height and one per
+    /// injection of a shorter height into the path, for both the trace batches and the FRI commit
+    /// phase.
+    fn measured_opening_permutations(proof: &Proof) -> usize {
+        let shard_proof = &proof.shard_proof;
+        let opening_proof = &shard_proof.opening_proof;
+        // The opened rows of each batch come in chip order, so they can be grouped by height.
+        let log_degrees: Vec<_> = shard_proof
+            .opened_values
+            .chips
+            .iter()
+            .map(|chip| chip.log_degree)
+            .collect();
+        let mut permutations = 0;
+        for (batches, query_proof) in opening_proof
+            .query_openings
+            .iter()
+            .zip(&opening_proof.fri_proof.query_proofs)
+        {
+            for batch in batches {
+                let chunks = batch.opened_values.len() / log_degrees.len();
+                let mut heights: Vec<(usize, usize)> = Vec::new();
+                for (rows, log_degree) in batch.opened_values.chunks(chunks).zip(&log_degrees) {
+                    let width: usize = rows.iter().map(|row| row.len()).sum();
+                    match heights.iter_mut().find(|(h, _)| h == log_degree) {
+                        Some((_, w)) => *w += width,
+                        None => heights.push((*log_degree, width)),
+                    }
+                }
+                permutations += batch.opening_proof.len() + heights.len() - 1;
+                permutations += heights
+                    .iter()
+                    .map(|(_, width)| width.div_ceil(POSEIDON2_RATE))
+                    .sum::<usize>();
+            }
+            for step in &query_proof.commit_phase_openings {
+                permutations += 1 + step.opening_proof.len();
+            }
+        }
+        permutations
+    }
+
+    #[test]
+    fn predict_the_merkle_openings_of_a_real_proof_from_the_vk() {
+        let proof = valid_proof();
+        let shape = ProofShape::new(&proof.vk).unwrap();
+        let predicted = shape.num_queries
+            * (shape.query_batch_opening_permutations() + shape.query_fri_opening_permutations());
+        assert_eq!(predicted, measured_opening_permutations(&proof));
+        assert_eq!(predicted, 20500);
+    }
+
+    #[test]
+    fn ignore_the_shape_claimed_by_the_proof() {
+        let proof = valid_proof();
+        let mut tampered = proof.clone();
+        let fri_proof = &mut tampered.shard_proof.opening_proof.fri_proof;
+        fri_proof.query_proofs.truncate(1);
+        fri_proof.commit_phase_commits.truncate(1);
+        tampered.shard_proof.opened_values.chips.truncate(1);
+        assert_eq!(
+            estimate_cost(&tampered, 32).unwrap(),
+            estimate_cost(&proof, 32).unwrap()
+        );
+    }
+
+    #[test]
+    fn grow_with_trace_heights() {
+        let proof = valid_proof();
+        let mut taller = proof.clone();
+        taller.vk.chip_information[0].1.log_n += 1;
+        let cost = shrink_proof_cost(&proof.vk).unwrap();
+        let taller_cost = shrink_proof_cost(&taller.vk).unwrap();
+        assert!(taller_cost.poseidon2_permutations > cost.poseidon2_permutations);
+        assert!(taller_cost.field_ops > cost.field_ops);
+    }
+
+    #[test]
+    fn reject_vks_with_chips_unknown_to_the_shrink_machine() {
+        let mut proof = valid_proof();
+        proof.vk.chip_information[0].0.push_str("Unknown");
+        assert!(matches!(
+            estimate_cost(&proof, 32),
+            Err(MachineVerificationError::InvalidVerificationKey)
+        ));
+    }
+
+    /// Collects what each stage of the shrink STARK verification spent.
+    #[derive(Default)]
+    struct Recorder {
+        spent: [VerificationCost; Stage::ALL.len()],
+    }
+
+    impl Meter for Recorder {
+        type Error = Error;
+
+        const NEEDS_COST: bool = false;
+
+        fn charge(
+            &mut self,
+            _stage: Stage,
+            _cost: impl FnOnce() -> VerificationCost,
+        ) -> Result<(), Error> {
+            Ok(())
+        }
+
+        fn spend(
+            &mut self,
+            stage: Stage,
+            spent: impl FnOnce() -> StageMetrics,
+        ) -> Result<(), Error> {
+            self.spent[stage as usize] += spent().cost;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn match_what_each_shrink_stage_spends() {
+        let proof = valid_proof();
+        let backend = NativeBackend::default();
+        let mut recorder = Recorder::default();
+        verify_shrink_proof(
+            &CountingBackend::new(&backend),
+            &proof.vk,
+            &proof.shard_proof,
+            &mut recorder,
+        )
+        .unwrap();
+
+        // The PCS and FRI stages count the permutations the backend ran and the field operations
+        // where they're performed. Only the constraints count theirs from the same symbolic form
+        // as the estimate.
+        let shape = ProofShape::new(&proof.vk).unwrap();
+        assert_eq!(recorder.spent[Stage::Pcs as usize], shape.pcs_cost());
+        assert_eq!(recorder.spent[Stage::Fri as usize], shape.fri_cost());
+        assert_eq!(
+            recorder.spent[Stage::Constraints as usize],
+            shape.constraints_cost()
+        );
+    }
+
+    #[test]
+    fn count_operations_shared_by_cloned_expressions_once() {
+        let variable = || SymbolicExpression::<BabyBear>::Constant(BabyBear::one());
+        let sum = variable() + variable();
+        // One addition, reused by a multiplication.
+        let square = sum.clone() * sum.clone();
+        assert_eq!(symbolic_ops(&square, &mut BTreeSet::new()), 2);
+        // Two additions computed independently, then multiplied.
+        let product = (variable() + variable()) * (variable() + variable());
+        assert_eq!(symbolic_ops(&product, &mut BTreeSet::new()), 3);
+        assert_eq!(symbolic_ops(&variable(), &mut BTreeSet::new()), 0);
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    Error, InnerSC, Proof, RecursionVkHash, ShardProof,
+    hash::{CountingBackend, HashBackend},
+    merkle::MerkleProof,
+    verifier::{
+        check_public_values, check_recursion_vk_membership, hash_babybear, verify_shrink_proof,
+    },
+};
+use serde::{Deserialize, Serialize};
+use sp1_stark_no_std::StarkVerifyingKey;
+
+/// A recursion verifying key whose membership in the recursion vk root has already been checked.
+///
+/// Registering a vk once lets [`verify_detached`] skip both hashing the vk and walking its Merkle
+/// path for every proof. It serializes as the vk along with its hash, the handle identifying it in
+/// the recursion vk Merkle tree, and deserializing it trusts that handle without checking anything
+/// again: only deserialize bytes that were written after [`RegisteredRecursionVk::register`]
+/// succeeded, e.g. from chain storage.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisteredRecursionVk {
+    vk: StarkVerifyingKey<InnerSC>,
+    hash: RecursionVkHash,
+}
+
+impl RegisteredRecursionVk {
+    /// Check that `vk` is a member of the recursion vk root according to `vk_merkle_proof`,
+    /// hashing with `backend`.
+    pub fn register<H: HashBackend>(
+        backend: &H,
+        vk: StarkVerifyingKey<InnerSC>,
+        vk_merkle_proof: &MerkleProof,
+    ) -> Result<Self, Error> {
+        let backend = &CountingBackend::new(backend);
+        let hash = hash_babybear(backend, &vk);
+        check_recursion_vk_membership(backend, hash, vk_merkle_proof, &mut ())?;
+        Ok(Self { vk, hash })
+    }
+
+    /// The registered recursion verifying key.
+    pub fn vk(&self) -> &StarkVerifyingKey<InnerSC> {
+        &self.vk
+    }
+
+    /// The hash identifying the registered vk inside the recursion vk Merkle tree.
+    pub fn hash(&self) -> RecursionVkHash {
+        self.hash
+    }
+}
+
+/// A shrink proof stripped of its recursion verifying key and vk Merkle proof.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetachedProof {
+    pub shard_proof: ShardProof,
+}
+
+impl Proof {
+    /// Split the proof into its detached part, its recursion verifying key and the Merkle proof
+    /// of that key.
+    pub fn detach(self) -> (DetachedProof, StarkVerifyingKey<InnerSC>, MerkleProof) {
+        let Proof {
+            shard_proof,
+            vk,
+            vk_merkle_proof,
+        } = self;
+        (DetachedProof { shard_proof }, vk, vk_merkle_proof)
+    }
+}
+
+/// Verify a [`DetachedProof`] against an already [`RegisteredRecursionVk`], computing every hash
+/// with `backend`.
+pub fn verify_detached<H: HashBackend>(
+    backend: &H,
+    registered_vk: &RegisteredRecursionVk,
+    vkey_digest: &[u8; 32],
+    proof: &DetachedProof,
+    inputs: &[u8],
+) -> Result<(), Error> {
+    let backend = &CountingBackend::new(backend);
+    check_public_values(backend, vkey_digest, &proof.shard_proof, inputs, &mut ())?;
+    verify_shrink_proof(backend, &registered_vk.vk, &proof.shard_proof, &mut ())
+}
@@ -0,0 +1,224 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{
+    ShardProof,
+    challenger::Challenger,
+    hash::HashBackend,
+    merkle::{MerkleOpening, verify_batch_openings},
+    pcs::{Challenge, ReducedOpenings, two_adic_power},
+};
+use alloc::vec::Vec;
+use core::{ops::Range, slice};
+use p3_baby_bear::BabyBear;
+use p3_field::{AbstractExtensionField, AbstractField, Field, TwoAdicField};
+use p3_fri::FriError;
+use p3_merkle_tree::FieldMerkleTreeError;
+use p3_util::reverse_bits_len;
+use sp1_stark_no_std::baby_bear_poseidon2::compressed_fri_config;
+use spin::Once;
+
+/// What the compressed FRI configuration of the shrink proofs fixes for the verifier.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FriParams {
+    pub(crate) log_blowup: usize,
+    pub(crate) num_queries: usize,
+    pub(crate) proof_of_work_bits: usize,
+}
+
+/// The parameters of the compressed FRI configuration, read from it on first use.
+pub(crate) fn fri_params() -> FriParams {
+    static FRI_PARAMS: Once<FriParams> = Once::new();
+    *FRI_PARAMS.call_once(|| {
+        let config = compressed_fri_config();
+        FriParams {
+            log_blowup: config.log_blowup,
+            num_queries: config.num_queries,
+            proof_of_work_bits: config.proof_of_work_bits,
+        }
+    })
+}
+
+/// Why the FRI commit phase of a shrink proof doesn't hold.
+pub(crate) type FoldingError = FriError<FieldMerkleTreeError>;
+
+/// What the transcript draws for the FRI commit phase.
+pub(crate) struct FriChallenges {
+    /// One folding challenge per commit phase round.
+    pub(crate) betas: Vec<Challenge>,
+    pub(crate) query_indices: Vec<usize>,
+    /// Log2 of the height of the tallest low degree extension.
+    pub(crate) log_max_height: usize,
+}
+
+/// Observe the commit phase and sample its challenges, checking the shape of the FRI proof and
+/// its proof of work along the way, like Plonky3's `verify_shape_and_sample_challenges`.
+pub(crate) fn sample_challenges<H: HashBackend>(
+    challenger: &mut Challenger<'_, H>,
+    shard_proof: &ShardProof,
+    params: FriParams,
+) -> Result<FriChallenges, FoldingError> {
+    let fri_proof = &shard_proof.opening_proof.fri_proof;
+    let betas = fri_proof
+        .commit_phase_commits
+        .iter()
+        .map(|commit| {
+            challenger.observe_slice(commit.as_ref());
+            challenger.sample_ext()
+        })
+        .collect();
+    challenger.observe_ext(fri_proof.final_poly);
+
+    if fri_proof.query_proofs.len() != params.num_queries {
+        return Err(FriError::InvalidProofShape);
+    }
+    if !challenger.check_witness(params.proof_of_work_bits, fri_proof.pow_witness) {
+        return Err(FriError::InvalidPowWitness);
+    }
+
+    let log_max_height = fri_proof.commit_phase_commits.len() + params.log_blowup;
+    if log_max_height > BabyBear::TWO_ADICITY {
+        return Err(FriError::InvalidProofShape);
+    }
+    let query_indices = (0..params.num_queries)
+        .map(|_| challenger.sample_bits(log_max_height))
+        .collect();
+    Ok(FriChallenges {
+        betas,
+        query_indices,
+        log_max_height,
+    })
+}
+
+/// Queries folded down through the commit phase, a round at a time, like Plonky3's
+/// `verify_challenges` folds each query through all the rounds.
+pub(crate) struct Folding {
+    queries: Range<usize>,
+    folded: Vec<Challenge>,
+    xs: Vec<BabyBear>,
+    indices: Vec<usize>,
+}
+
+impl Folding {
+    /// Start each of `queries`, out of all the queries of the proof, at its index, before the
+    /// first round. Also returns the field operations spent.
+    pub(crate) fn new(
+        shard_proof: &ShardProof,
+        challenges: &FriChallenges,
+        queries: Range<usize>,
+    ) -> Result<(Self, u64), FoldingError> {
+        let fri_proof = &shard_proof.opening_proof.fri_proof;
+        if fri_proof.query_proofs[queries.clone()]
+            .iter()
+            .any(|query_proof| {
+                query_proof.commit_phase_openings.len() != fri_proof.commit_phase_commits.len()
+            })
+        {
+            return Err(FriError::InvalidProofShape);
+        }
+
+        let log_max_height = challenges.log_max_height;
+        let indices = challenges.query_indices[queries.clone()].to_vec();
+        let xs = indices
+            .iter()
+            .map(|&index| two_adic_power(log_max_height, reverse_bits_len(index, log_max_height)))
+            .collect::<Vec<_>>();
+        let field_ops = (2 * log_max_height * xs.len()) as u64;
+        let folding = Self {
+            queries,
+            folded: alloc::vec![Challenge::zero(); xs.len()],
+            xs,
+            indices,
+        };
+        Ok((folding, field_ops))
+    }
+
+    /// Fold every query through round `round`, checking the openings of the sibling values it
+    /// pairs them with. Returns the field operations spent.
+    pub(crate) fn fold_round<H: HashBackend>(
+        &mut self,
+        backend: &H,
+        shard_proof: &ShardProof,
+        challenges: &FriChallenges,
+        reduced_openings: &ReducedOpenings,
+        round: usize,
+    ) -> Result<u64, FoldingError> {
+        let fri_proof = &shard_proof.opening_proof.fri_proof;
+        let log_folded_height = challenges.log_max_height - round - 1;
+        let beta = challenges.betas[round];
+
+        let mut evals = Vec::with_capacity(self.folded.len());
+        let mut rows = Vec::with_capacity(self.folded.len());
+        let query_proofs = &fri_proof.query_proofs[self.queries.clone()];
+        for (query, (folded, &index)) in self.folded.iter_mut().zip(&self.indices).enumerate() {
+            *folded += reduced_openings.of_query(query)[log_folded_height + 1];
+            let step = &query_proofs[query].commit_phase_openings[round];
+            let mut pair = [*folded; 2];
+            pair[(index ^ 1) % 2] = step.sibling_value;
+            let mut row =
+                [BabyBear::zero(); 2 * <Challenge as AbstractExtensionField<BabyBear>>::D];
+            let (low, high) = row.split_at_mut(row.len() / 2);
+            low.copy_from_slice(pair[0].as_base_slice());
+            high.copy_from_slice(pair[1].as_base_slice());
+            evals.push(pair);
+            rows.push(row);
+        }
+
+        let openings: Vec<_> = rows
+            .iter()
+            .zip(&self.indices)
+            .enumerate()
+            .map(|(query, (row, &index))| MerkleOpening {
+                index: index >> 1,
+                rows: slice::from_ref(row),
+                path: &query_proofs[query].commit_phase_openings[round].opening_proof,
+            })
+            .collect();
+        verify_batch_openings(
+            backend,
+            fri_proof.commit_phase_commits[round].as_ref(),
+            &[1 << log_folded_height],
+            &openings,
+        )
+        .map_err(FriError::CommitPhaseMmcsError)?;
+
+        for (((folded, x), index), pair) in self
+            .folded
+            .iter_mut()
+            .zip(&mut self.xs)
+            .zip(&mut self.indices)
+            .zip(evals)
+        {
+            let mut xs = [*x; 2];
+            xs[(*index ^ 1) % 2] *= BabyBear::two_adic_generator(1);
+            let inverse = (xs[1] - xs[0]).inverse();
+            *folded =
+                pair[0] + (beta - Challenge::from_base(xs[0])) * (pair[1] - pair[0]) * inverse;
+            *index >>= 1;
+            *x = x.square();
+        }
+        // One addition of the reduced opening, then the folding above.
+        Ok(10 * self.folded.len() as u64)
+    }
+
+    /// Check that every query folded down to the final polynomial.
+    pub(crate) fn check_final_poly(&self, shard_proof: &ShardProof) -> Result<(), FoldingError> {
+        let final_poly = shard_proof.opening_proof.fri_proof.final_poly;
+        if self.folded.iter().all(|folded| *folded == final_poly) {
+            Ok(())
+        } else {
+            Err(FriError::FinalPolyMismatch)
+        }
+    }
+}
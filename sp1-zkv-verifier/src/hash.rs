@@ -0,0 +1,264 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    VerificationCost,
+    cost::{blake3_cost, sha256_cost},
+    meter::StageMetrics,
+};
+use alloc::vec::Vec;
+use core::cell::Cell;
+use p3_baby_bear::BabyBear;
+use p3_field::AbstractField;
+use p3_symmetric::Permutation;
+use sha2::{Digest, Sha256};
+use sp1_stark_no_std::{DIGEST_SIZE, InnerPerm, inner_perm};
+
+/// Width of the Poseidon2 permutation over BabyBear.
+pub const POSEIDON2_WIDTH: usize = 16;
+/// Rate of the Poseidon2 sponge.
+const POSEIDON2_RATE: usize = 8;
+
+/// The hash primitives used by the verifier, including the transcript and the Merkle openings of
+/// the shrink STARK.
+///
+/// [`NativeBackend`] implements them in pure Rust. Environments with faster implementations,
+/// like Substrate host functions or zkVM precompiles, can supply their own to
+/// [`crate::verify_with_backend`].
+pub trait HashBackend {
+    /// SHA-256 digest of `data`.
+    fn sha256(&self, data: &[u8]) -> [u8; 32];
+
+    /// BLAKE3 digest of `data`.
+    fn blake3(&self, data: &[u8]) -> [u8; 32];
+
+    /// Apply the SP1 Poseidon2 permutation over BabyBear to `state` in place.
+    fn poseidon2_permute(&self, state: &mut [BabyBear; POSEIDON2_WIDTH]);
+
+    /// Apply the permutation to every state of `states`, which are independent of each other.
+    /// The Merkle openings of all the queries of a proof go through here, so backends able to
+    /// run several permutations at once should override it.
+    fn poseidon2_permute_many(&self, states: &mut [[BabyBear; POSEIDON2_WIDTH]]) {
+        states
+            .iter_mut()
+            .for_each(|state| self.poseidon2_permute(state));
+    }
+}
+
+/// Pure Rust [`HashBackend`], used by [`crate::verify`].
+#[derive(Clone)]
+pub struct NativeBackend {
+    perm: InnerPerm,
+}
+
+impl Default for NativeBackend {
+    fn default() -> Self {
+        Self { perm: inner_perm() }
+    }
+}
+
+impl HashBackend for NativeBackend {
+    fn sha256(&self, data: &[u8]) -> [u8; 32] {
+        Sha256::digest(data).into()
+    }
+
+    fn blake3(&self, data: &[u8]) -> [u8; 32] {
+        blake3::hash(data).into()
+    }
+
+    fn poseidon2_permute(&self, state: &mut [BabyBear; POSEIDON2_WIDTH]) {
+        self.perm.permute_mut(state);
+    }
+}
+
+/// A [`HashBackend`] counting the work done through another one, so that the verification can
+/// report what each stage actually spent.
+pub(crate) struct CountingBackend<'a, H> {
+    inner: &'a H,
+    spent: Cell<StageMetrics>,
+}
+
+impl<'a, H: HashBackend> CountingBackend<'a, H> {
+    pub(crate) fn new(inner: &'a H) -> Self {
+        Self {
+            inner,
+            spent: Cell::default(),
+        }
+    }
+
+    /// What was spent since the last call.
+    pub(crate) fn take(&self) -> StageMetrics {
+        self.spent.take()
+    }
+
+    fn count(&self, cost: VerificationCost) {
+        let mut spent = self.spent.get();
+        spent += StageMetrics {
+            cost,
+            hash_invocations: 1,
+        };
+        self.spent.set(spent);
+    }
+}
+
+impl<H: HashBackend> HashBackend for CountingBackend<'_, H> {
+    fn sha256(&self, data: &[u8]) -> [u8; 32] {
+        self.count(sha256_cost(data.len()));
+        self.inner.sha256(data)
+    }
+
+    fn blake3(&self, data: &[u8]) -> [u8; 32] {
+        self.count(blake3_cost(data.len()));
+        self.inner.blake3(data)
+    }
+
+    fn poseidon2_permute(&self, state: &mut [BabyBear; POSEIDON2_WIDTH]) {
+        self.count(VerificationCost {
+            poseidon2_permutations: 1,
+            ..Default::default()
+        });
+        self.inner.poseidon2_permute(state);
+    }
+
+    fn poseidon2_permute_many(&self, states: &mut [[BabyBear; POSEIDON2_WIDTH]]) {
+        self.count(VerificationCost {
+            poseidon2_permutations: states.len() as u64,
+            ..Default::default()
+        });
+        self.inner.poseidon2_permute_many(states);
+    }
+}
+
+/// Hash `input` with the padding-free Poseidon2 sponge used across SP1.
+pub(crate) fn poseidon2_hash<H: HashBackend>(
+    backend: &H,
+    input: impl IntoIterator<Item = BabyBear>,
+) -> [BabyBear; DIGEST_SIZE] {
+    let mut state = [BabyBear::zero(); POSEIDON2_WIDTH];
+    let mut input = input.into_iter().peekable();
+    while input.peek().is_some() {
+        for (slot, x) in state.iter_mut().take(POSEIDON2_RATE).zip(input.by_ref()) {
+            *slot = x;
+        }
+        backend.poseidon2_permute(&mut state);
+    }
+    state[..DIGEST_SIZE].try_into().unwrap()
+}
+
+/// Compress two digests into one, as done by the SP1 Merkle trees.
+pub(crate) fn poseidon2_compress<H: HashBackend>(
+    backend: &H,
+    input: [[BabyBear; DIGEST_SIZE]; 2],
+) -> [BabyBear; DIGEST_SIZE] {
+    let mut pre_iter = input.into_iter().flatten();
+    let mut pre = core::array::from_fn(move |_| pre_iter.next().unwrap());
+    backend.poseidon2_permute(&mut pre);
+    pre[..DIGEST_SIZE].try_into().unwrap()
+}
+
+/// Hash every input like [`poseidon2_hash`], running the permutations of all of them together.
+pub(crate) fn poseidon2_hash_many<H: HashBackend, I: Iterator<Item = BabyBear>>(
+    backend: &H,
+    inputs: impl IntoIterator<Item = I>,
+) -> Vec<[BabyBear; DIGEST_SIZE]> {
+    let mut inputs: Vec<_> = inputs.into_iter().map(Iterator::peekable).collect();
+    let mut states = alloc::vec![[BabyBear::zero(); POSEIDON2_WIDTH]; inputs.len()];
+    let mut pending = Vec::with_capacity(inputs.len());
+    let mut batch = Vec::with_capacity(inputs.len());
+    loop {
+        // Inputs of different lengths are done at different steps.
+        pending.clear();
+        batch.clear();
+        for (lane, (input, state)) in inputs.iter_mut().zip(&mut states).enumerate() {
+            if input.peek().is_none() {
+                continue;
+            }
+            for (slot, x) in state.iter_mut().take(POSEIDON2_RATE).zip(input.by_ref()) {
+                *slot = x;
+            }
+            pending.push(lane);
+            batch.push(*state);
+        }
+        if batch.is_empty() {
+            break;
+        }
+        backend.poseidon2_permute_many(&mut batch);
+        for (&lane, state) in pending.iter().zip(&batch) {
+            states[lane] = *state;
+        }
+    }
+    states
+        .iter()
+        .map(|state| state[..DIGEST_SIZE].try_into().unwrap())
+        .collect()
+}
+
+/// Compress every pair of digests like [`poseidon2_compress`], running the permutations of all
+/// of them together.
+pub(crate) fn poseidon2_compress_many<H: HashBackend>(
+    backend: &H,
+    pairs: impl IntoIterator<Item = [[BabyBear; DIGEST_SIZE]; 2]>,
+) -> Vec<[BabyBear; DIGEST_SIZE]> {
+    let mut states: Vec<[BabyBear; POSEIDON2_WIDTH]> = pairs
+        .into_iter()
+        .map(|pair| {
+            let mut pair = pair.into_iter().flatten();
+            core::array::from_fn(|_| pair.next().unwrap())
+        })
+        .collect();
+    backend.poseidon2_permute_many(&mut states);
+    states
+        .iter()
+        .map(|state| state[..DIGEST_SIZE].try_into().unwrap())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p3_symmetric::CryptographicHasher;
+    use sp1_stark_no_std::InnerHash;
+
+    #[test]
+    fn poseidon2_hash_matches_padding_free_sponge() {
+        let backend = NativeBackend::default();
+        let hasher = InnerHash::new(inner_perm());
+        for len in [0, 1, 7, 8, 9, 16, 17, 100] {
+            let input: alloc::vec::Vec<_> = (0..len).map(BabyBear::from_canonical_u32).collect();
+            assert_eq!(
+                poseidon2_hash(&backend, input.iter().copied()),
+                hasher.hash_slice(&input),
+                "input length {len}"
+            );
+        }
+    }
+
+    #[test]
+    fn hash_many_like_one_at_a_time() {
+        let backend = NativeBackend::default();
+        let inputs: alloc::vec::Vec<alloc::vec::Vec<_>> = [0, 1, 8, 9, 17, 3]
+            .into_iter()
+            .map(|len| (0..len).map(BabyBear::from_canonical_u32).collect())
+            .collect();
+        let expected: alloc::vec::Vec<_> = inputs
+            .iter()
+            .map(|input| poseidon2_hash(&backend, input.iter().copied()))
+            .collect();
+        assert_eq!(
+            poseidon2_hash_many(&backend, inputs.iter().map(|input| input.iter().copied())),
+            expected
+        );
+    }
+}
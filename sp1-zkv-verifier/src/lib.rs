@@ -17,14 +17,68 @@
 
 extern crate alloc;
 
+mod aggregation;
+#[cfg(feature = "arena")]
+mod arena;
+#[cfg(feature = "attestation")]
+mod attestation;
+mod cache;
+mod challenger;
 mod checks;
+mod cost;
+mod detached;
+mod fri;
+mod hash;
 mod merkle;
+mod meter;
+mod metered;
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(feature = "packed")]
+mod packed;
+mod pcs;
+mod statement;
 mod verifier;
 mod vks;
 
 type InnerSC = sp1_stark_no_std::baby_bear_poseidon2::BabyBearPoseidon2;
 pub type ShardProof = sp1_stark_no_std::ShardProof<InnerSC>;
 pub type Error = sp1_stark_no_std::MachineVerificationError<InnerSC>;
+pub type RecursionVkHash = [p3_baby_bear::BabyBear; sp1_stark_no_std::DIGEST_SIZE];
 
+/// Upper bound on the heap used to verify a standard shrink proof, on top of the shrink machine
+/// built by the first verification and kept afterwards. `tests/allocations.rs` fails if the peak
+/// heap of a verification exceeds it.
+///
+/// Both bounds are checked on 64-bit hosts. On 32-bit targets like `thumbv7em-none-eabi`, only
+/// pointer-sized values get smaller, so the heap bound holds there too, while the stack bound
+/// should be confirmed on the target.
+pub const MAX_VERIFY_HEAP: usize = if cfg!(feature = "small-footprint") {
+    512 << 10
+} else {
+    1 << 20
+};
+/// Upper bound on the stack used to verify a standard shrink proof, checked by `tests/arena.rs`.
+pub const MAX_VERIFY_STACK: usize = 2 << 20;
+
+pub use aggregation::{aggregation_root, is_statement_included};
+#[cfg(feature = "arena")]
+pub use arena::{Arena, ArenaError, verify_in_arena};
+#[cfg(feature = "attestation")]
+pub use attestation::{Attestation, verify_and_attest, verify_attestation};
+pub use cache::RecursionVkCache;
+pub use cost::{VerificationCost, estimate_cost};
+pub use detached::{DetachedProof, RegisteredRecursionVk, verify_detached};
+pub use hash::{HashBackend, NativeBackend, POSEIDON2_WIDTH};
 pub use merkle::MerkleProof;
-pub use verifier::{Proof, verify};
+pub use meter::{Stage, StageMetrics};
+pub use metered::{MeteredError, verify_metered};
+#[cfg(feature = "metrics")]
+pub use metrics::{VerificationMetrics, verify_instrumented};
+#[cfg(feature = "packed")]
+pub use packed::{PackedBackend, are_merkle_paths_valid, recursion_vk_hashes, verify_batch};
+pub use statement::{HashKind, SP1_CONTEXT, VerifiedStatement, statement_hash, statement_leaf};
+pub use verifier::{
+    NUM_PUBLIC_VALUES, PUBLIC_INPUTS_MISMATCH, Proof, claimed_vkey_digest, recursion_vk_hash,
+    verify, verify_cached, verify_statement, verify_with_backend,
+};
@@ -1,10 +1,11 @@
-use alloc::vec::Vec;
+use crate::hash::{HashBackend, poseidon2_compress, poseidon2_compress_many, poseidon2_hash_many};
+use alloc::{vec, vec::Vec};
 use core::fmt::Debug;
 use p3_baby_bear::BabyBear;
-use p3_symmetric::Permutation;
+use p3_merkle_tree::FieldMerkleTreeError;
 use p3_util::reverse_bits_len;
 use serde::{Deserialize, Serialize};
-use sp1_stark_no_std::{DIGEST_SIZE, inner_perm};
+use sp1_stark_no_std::DIGEST_SIZE;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MerkleProof {
@@ -12,18 +13,21 @@ pub struct MerkleProof {
     pub path: Vec<[BabyBear; DIGEST_SIZE]>,
 }
 
-fn constant_compress(input: [[BabyBear; DIGEST_SIZE]; 2]) -> [BabyBear; DIGEST_SIZE] {
-    let mut pre_iter = input.into_iter().flatten();
-    let mut pre = core::array::from_fn(move |_| pre_iter.next().unwrap());
-    inner_perm().permute_mut(&mut pre);
-    pre[..8].try_into().unwrap()
+pub fn is_merkle_path_valid<H: HashBackend>(
+    backend: &H,
+    proof: &MerkleProof,
+    value: [BabyBear; DIGEST_SIZE],
+    commitment: [BabyBear; DIGEST_SIZE],
+) -> bool {
+    merkle_root(backend, proof, value) == commitment
 }
 
-pub fn is_merkle_path_valid(
+/// Compute the root of the Merkle tree in which `value` sits according to `proof`.
+pub(crate) fn merkle_root<H: HashBackend>(
+    backend: &H,
     proof: &MerkleProof,
     mut value: [BabyBear; DIGEST_SIZE],
-    commitment: [BabyBear; DIGEST_SIZE],
-) -> bool {
+) -> [BabyBear; DIGEST_SIZE] {
     let MerkleProof { index, path } = proof;
 
     let mut index = reverse_bits_len(*index, path.len());
@@ -34,9 +38,206 @@ pub fn is_merkle_path_valid(
         } else {
             [sibling, value]
         };
-        value = constant_compress(new_pair);
+        value = poseidon2_compress(backend, new_pair);
         index >>= 1;
     }
 
-    value == commitment
+    value
+}
+
+/// The opening of row `index` in every matrix of a committed batch: one row per matrix, and the
+/// siblings of the path to the root.
+pub(crate) struct MerkleOpening<'a, R> {
+    pub(crate) index: usize,
+    pub(crate) rows: &'a [R],
+    pub(crate) path: &'a [[BabyBear; DIGEST_SIZE]],
+}
+
+/// Check `openings` of a batch of matrices of `heights` committed to `commit`, like Plonky3's
+/// `FieldMerkleTreeMmcs::verify_batch` checks each of them.
+///
+/// The rows of the tallest matrices are hashed into a leaf, and the rows of each shorter height
+/// are hashed and compressed into the path once it climbs to that height. All the openings are
+/// walked together, so that the permutations of each step run in a single backend call.
+pub(crate) fn verify_batch_openings<H: HashBackend, R: AsRef<[BabyBear]>>(
+    backend: &H,
+    commit: &[BabyBear],
+    heights: &[usize],
+    openings: &[MerkleOpening<'_, R>],
+) -> Result<(), FieldMerkleTreeError> {
+    if openings
+        .iter()
+        .any(|opening| opening.rows.len() != heights.len())
+    {
+        return Err(FieldMerkleTreeError::WrongBatchSize);
+    }
+    let groups = hashed_groups(heights).ok_or(FieldMerkleTreeError::WrongBatchSize)?;
+    let hash_group = |group: &[usize], lanes: &[usize]| {
+        poseidon2_hash_many(
+            backend,
+            lanes.iter().map(|&lane| {
+                group
+                    .iter()
+                    .flat_map(move |&matrix| openings[lane].rows[matrix].as_ref().iter().copied())
+            }),
+        )
+    };
+
+    let all_lanes: Vec<usize> = (0..openings.len()).collect();
+    let mut roots = hash_group(&groups[0].1, &all_lanes);
+    let depth = openings
+        .iter()
+        .map(|opening| opening.path.len())
+        .max()
+        .unwrap_or_default();
+    let mut lanes = Vec::with_capacity(openings.len());
+    for level in 0..depth {
+        // Openings with a shorter path already have their root.
+        lanes.clear();
+        lanes.extend((0..openings.len()).filter(|&lane| level < openings[lane].path.len()));
+        let compressed = poseidon2_compress_many(
+            backend,
+            lanes.iter().map(|&lane| {
+                let sibling = openings[lane].path[level];
+                if (openings[lane].index >> level) & 1 == 0 {
+                    [roots[lane], sibling]
+                } else {
+                    [sibling, roots[lane]]
+                }
+            }),
+        );
+        for (&lane, root) in lanes.iter().zip(compressed) {
+            roots[lane] = root;
+        }
+
+        if let Some((_, group)) = groups.iter().find(|(at, _)| *at == level + 1) {
+            let digests = hash_group(group, &lanes);
+            let injected = poseidon2_compress_many(
+                backend,
+                lanes
+                    .iter()
+                    .zip(&digests)
+                    .map(|(&lane, digest)| [roots[lane], *digest]),
+            );
+            for (&lane, root) in lanes.iter().zip(injected) {
+                roots[lane] = root;
+            }
+        }
+    }
+
+    if roots.iter().all(|root| root.as_slice() == commit) {
+        Ok(())
+    } else {
+        Err(FieldMerkleTreeError::RootMismatch)
+    }
+}
+
+/// The matrices hashed together at each level of the path, starting from the leaf at level 0,
+/// or `None` for an empty batch.
+///
+/// Like Plonky3, the leaf gathers the matrices whose height rounds up to the tallest, and each
+/// later level the matrices of the first remaining height once it rounds up to the height of the
+/// path there. Matrices that never fit are never hashed.
+fn hashed_groups(heights: &[usize]) -> Option<Vec<(usize, Vec<usize>)>> {
+    let mut tallest_first: Vec<usize> = (0..heights.len()).collect();
+    // Stable, so that matrices of the same height keep their order.
+    tallest_first.sort_by_key(|&matrix| core::cmp::Reverse(heights[matrix]));
+    let mut remaining = tallest_first.into_iter().peekable();
+
+    let mut height = heights[*remaining.peek()?].next_power_of_two();
+    let mut groups = vec![(
+        0,
+        take_while_peeking(&mut remaining, |matrix| {
+            heights[matrix].next_power_of_two() == height
+        }),
+    )];
+    let mut level = 0;
+    while let Some(&next) = remaining.peek() {
+        level += 1;
+        height >>= 1;
+        if height == 0 {
+            break;
+        }
+        let next_height = heights[next];
+        if next_height.next_power_of_two() == height {
+            let group = take_while_peeking(&mut remaining, |matrix| heights[matrix] == next_height);
+            groups.push((level, group));
+        }
+    }
+    Some(groups)
+}
+
+fn take_while_peeking(
+    iter: &mut core::iter::Peekable<impl Iterator<Item = usize>>,
+    mut predicate: impl FnMut(usize) -> bool,
+) -> Vec<usize> {
+    let mut taken = Vec::new();
+    while let Some(matrix) = iter.next_if(|&matrix| predicate(matrix)) {
+        taken.push(matrix);
+    }
+    taken
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::{NativeBackend, poseidon2_hash};
+    use p3_field::AbstractField;
+
+    fn row(seed: u32, width: u32) -> Vec<BabyBear> {
+        (0..width)
+            .map(|i| BabyBear::from_canonical_u32(seed * 100 + i))
+            .collect()
+    }
+
+    #[test]
+    fn group_matrices_like_plonky3() {
+        // Heights rounding up to the tallest share the leaf.
+        assert_eq!(
+            hashed_groups(&[4, 8, 6, 2, 1]),
+            Some(vec![
+                (0, vec![1, 2]),
+                (1, vec![0]),
+                (2, vec![3]),
+                (3, vec![4])
+            ])
+        );
+        // Only the first of two heights rounding up to the same level is hashed.
+        assert_eq!(
+            hashed_groups(&[8, 4, 3]),
+            Some(vec![(0, vec![0]), (1, vec![1])])
+        );
+        assert_eq!(hashed_groups(&[]), None);
+    }
+
+    #[test]
+    fn check_openings_of_mixed_heights() {
+        let backend = NativeBackend::default();
+        // One matrix of height 4 and one of height 2, opened at row 3 of the tallest.
+        let rows = [row(1, 10), row(2, 3)];
+        let siblings = [[BabyBear::from_canonical_u32(7); DIGEST_SIZE]; 2];
+        let leaf = poseidon2_hash(&backend, rows[0].iter().copied());
+        let node = poseidon2_compress(&backend, [siblings[0], leaf]);
+        let node = poseidon2_compress(
+            &backend,
+            [node, poseidon2_hash(&backend, rows[1].iter().copied())],
+        );
+        let root = poseidon2_compress(&backend, [siblings[1], node]);
+
+        let opening = MerkleOpening {
+            index: 3,
+            rows: &rows,
+            path: &siblings,
+        };
+        let openings = [opening];
+        assert!(verify_batch_openings(&backend, &root, &[4, 2], &openings).is_ok());
+        assert!(matches!(
+            verify_batch_openings(&backend, &root, &[4, 4], &openings),
+            Err(FieldMerkleTreeError::RootMismatch)
+        ));
+        assert!(matches!(
+            verify_batch_openings(&backend, &root, &[4], &openings),
+            Err(FieldMerkleTreeError::WrongBatchSize)
+        ));
+    }
 }
@@ -0,0 +1,126 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Error, VerificationCost};
+use core::ops::AddAssign;
+
+/// A stage of the verification, as accounted for by a [`Meter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    /// Hashing the public inputs and comparing them with the committed value digest.
+    PublicInputsHash,
+    /// Checking the sp1 vk digest, the recursion vk root and completeness.
+    VkDigest,
+    /// Recomputing the recursion public values digest.
+    PublicValuesDigest,
+    /// Hashing the recursion verifying key.
+    RecursionVkHash,
+    /// Walking the recursion vk Merkle path.
+    MerklePath,
+    /// Evaluating the shrink STARK constraints.
+    Constraints,
+    /// Hashing the transcript and checking the shrink STARK trace openings.
+    Pcs,
+    /// Checking the FRI commit phase of the shrink STARK.
+    Fri,
+}
+
+/// What a single stage of the verification spent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StageMetrics {
+    /// Primitive operations spent by the stage.
+    pub cost: VerificationCost,
+    /// Calls to the hash backend: SHA-256 or BLAKE3 digests, and Poseidon2 permutations, one at a
+    /// time or in batches.
+    pub hash_invocations: u64,
+}
+
+impl AddAssign for StageMetrics {
+    fn add_assign(&mut self, rhs: Self) {
+        self.cost += rhs.cost;
+        self.hash_invocations += rhs.hash_invocations;
+    }
+}
+
+impl From<VerificationCost> for StageMetrics {
+    /// Metrics of a stage that only calls Poseidon2, one permutation at a time.
+    fn from(cost: VerificationCost) -> Self {
+        Self {
+            cost,
+            hash_invocations: cost.poseidon2_permutations,
+        }
+    }
+}
+
+impl Stage {
+    pub const ALL: [Stage; 8] = [
+        Stage::PublicInputsHash,
+        Stage::VkDigest,
+        Stage::PublicValuesDigest,
+        Stage::RecursionVkHash,
+        Stage::MerklePath,
+        Stage::Constraints,
+        Stage::Pcs,
+        Stage::Fri,
+    ];
+}
+
+/// Observes the verification as it goes through its stages.
+///
+/// The unit meter does nothing and is what the plain verification entry points use, so that
+/// costs are never computed unless someone asks for them.
+pub(crate) trait Meter {
+    /// Error returned by the verification, which may also come from the meter itself.
+    type Error: From<Error>;
+
+    /// Whether [`Meter::charge`] ever evaluates the cost it's given.
+    const NEEDS_COST: bool;
+
+    /// Account for the cost of `stage`, right before running it. Failing aborts the verification.
+    fn charge(
+        &mut self,
+        stage: Stage,
+        cost: impl FnOnce() -> VerificationCost,
+    ) -> Result<(), Self::Error>;
+
+    /// Run `f` as the part of the verification called `name`.
+    fn run<T>(&mut self, _name: &'static str, f: impl FnOnce() -> T) -> T {
+        f()
+    }
+
+    /// Account for what `stage` actually spent since the last call, right after running part of
+    /// it. Failing aborts the verification.
+    fn spend(
+        &mut self,
+        _stage: Stage,
+        _spent: impl FnOnce() -> StageMetrics,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl Meter for () {
+    type Error = Error;
+
+    const NEEDS_COST: bool = false;
+
+    fn charge(
+        &mut self,
+        _stage: Stage,
+        _cost: impl FnOnce() -> VerificationCost,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+}
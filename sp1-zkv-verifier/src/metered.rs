@@ -0,0 +1,181 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    Error, Proof, VerificationCost,
+    hash::HashBackend,
+    meter::{Meter, Stage, StageMetrics},
+    verifier::verify_with_meter,
+};
+use core::fmt;
+
+/// Error returned by [`verify_metered`].
+#[derive(Debug)]
+pub enum MeteredError {
+    /// `stage` would have spent, or did spend, more than the budget. `spent` is what the
+    /// verification actually spent until then.
+    BudgetExceeded {
+        stage: Stage,
+        spent: VerificationCost,
+    },
+    /// The proof is invalid.
+    Verification(Error),
+}
+
+impl fmt::Display for MeteredError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MeteredError::BudgetExceeded { stage, spent } => write!(
+                f,
+                "verification budget exceeded at stage {stage:?}, after spending {spent:?}"
+            ),
+            MeteredError::Verification(error) => write!(f, "invalid proof: {error:?}"),
+        }
+    }
+}
+
+impl core::error::Error for MeteredError {}
+
+impl From<Error> for MeteredError {
+    fn from(error: Error) -> Self {
+        MeteredError::Verification(error)
+    }
+}
+
+/// Tracks what the verification charged and spent against a fixed budget.
+struct BudgetMeter {
+    budget: VerificationCost,
+    /// Estimated cost of the stages admitted so far.
+    charged: VerificationCost,
+    /// What the stages actually spent so far.
+    spent: VerificationCost,
+}
+
+impl BudgetMeter {
+    fn check(&self, stage: Stage, cost: VerificationCost) -> Result<(), MeteredError> {
+        if cost.fits_within(&self.budget) {
+            Ok(())
+        } else {
+            Err(MeteredError::BudgetExceeded {
+                stage,
+                spent: self.spent,
+            })
+        }
+    }
+}
+
+impl Meter for BudgetMeter {
+    type Error = MeteredError;
+
+    const NEEDS_COST: bool = true;
+
+    fn charge(
+        &mut self,
+        stage: Stage,
+        cost: impl FnOnce() -> VerificationCost,
+    ) -> Result<(), MeteredError> {
+        let charged = self.charged + cost();
+        self.check(stage, charged)?;
+        self.charged = charged;
+        Ok(())
+    }
+
+    fn spend(
+        &mut self,
+        stage: Stage,
+        spent: impl FnOnce() -> StageMetrics,
+    ) -> Result<(), MeteredError> {
+        self.spent += spent().cost;
+        self.check(stage, self.spent)
+    }
+}
+
+/// Verify a proof like [`crate::verify_with_backend`], refusing to run any stage whose cost
+/// doesn't fit in `budget`, and stopping as soon as the verification actually spends more.
+///
+/// Every stage is admitted before it runs, by adding its cost as [`crate::estimate_cost`] derives
+/// it from the lengths of the inputs, of the recursion vk and of its Merkle path to the cost of
+/// the stages before it. The shrink STARK stages are admitted at once, after the recursion vk
+/// membership has been checked. Then the hashes and field operations are counted as they run, and
+/// charged against the budget after each stage of the public values and the recursion vk, and
+/// after each step of the shrink STARK: the transcript, every trace batch opening, every FRI
+/// round and the constraints of every chip.
+///
+/// On success, return what the verification spent, which is the estimate for a valid proof.
+pub fn verify_metered<H: HashBackend>(
+    backend: &H,
+    vkey_digest: &[u8; 32],
+    proof: &Proof,
+    inputs: &[u8],
+    budget: VerificationCost,
+) -> Result<VerificationCost, MeteredError> {
+    let mut meter = BudgetMeter {
+        budget,
+        charged: VerificationCost::default(),
+        spent: VerificationCost::default(),
+    };
+    verify_with_meter(backend, vkey_digest, proof, inputs, &mut meter)?;
+    Ok(meter.spent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn permutations(poseidon2_permutations: u64) -> VerificationCost {
+        VerificationCost {
+            poseidon2_permutations,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn stop_once_a_stage_spends_more_than_the_budget() {
+        let mut meter = BudgetMeter {
+            budget: permutations(10),
+            charged: VerificationCost::default(),
+            spent: VerificationCost::default(),
+        };
+        // Admitted on an estimate that turns out too low.
+        meter.charge(Stage::Pcs, || permutations(8)).unwrap();
+        meter.spend(Stage::Pcs, || permutations(6).into()).unwrap();
+        assert!(matches!(
+            meter.spend(Stage::Pcs, || permutations(6).into()),
+            Err(MeteredError::BudgetExceeded {
+                stage: Stage::Pcs,
+                spent,
+            }) if spent == permutations(12)
+        ));
+    }
+
+    #[test]
+    fn admit_stages_on_their_cumulated_estimates() {
+        let mut meter = BudgetMeter {
+            budget: permutations(10),
+            charged: VerificationCost::default(),
+            spent: VerificationCost::default(),
+        };
+        meter
+            .charge(Stage::Constraints, || permutations(6))
+            .unwrap();
+        assert!(matches!(
+            meter.charge(Stage::Pcs, || permutations(6)),
+            Err(MeteredError::BudgetExceeded {
+                stage: Stage::Pcs,
+                ..
+            })
+        ));
+    }
+}
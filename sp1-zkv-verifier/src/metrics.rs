@@ -0,0 +1,103 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    Error, Proof, VerificationCost,
+    hash::HashBackend,
+    meter::{Meter, Stage, StageMetrics},
+    verifier::verify_with_meter,
+};
+
+/// Per-stage operation counters collected by [`verify_instrumented`].
+///
+/// Every stage is counted as it runs: hashes through the hash backend, and field operations by the
+/// PCS and FRI code that performs them. Only the constraint evaluation counts its field operations
+/// from the symbolic form of the constraints. A proof failing partway reports what the stages
+/// spent up to the last step that succeeded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerificationMetrics {
+    stages: [StageMetrics; Stage::ALL.len()],
+}
+
+impl VerificationMetrics {
+    /// What `stage` spent. Stages that were never reached report nothing.
+    pub fn stage(&self, stage: Stage) -> StageMetrics {
+        self.stages[stage as usize]
+    }
+
+    /// What the whole verification spent.
+    pub fn total(&self) -> StageMetrics {
+        let mut total = StageMetrics::default();
+        self.stages.iter().for_each(|stage| total += *stage);
+        total
+    }
+
+    fn add(&mut self, stage: Stage, spent: StageMetrics) {
+        tracing::trace!(
+            ?stage,
+            poseidon2_permutations = spent.cost.poseidon2_permutations,
+            field_ops = spent.cost.field_ops,
+            hash_invocations = spent.hash_invocations,
+            "sp1 verification stage"
+        );
+        self.stages[stage as usize] += spent;
+    }
+}
+
+/// Collects [`VerificationMetrics`].
+#[derive(Default)]
+struct Instrument {
+    metrics: VerificationMetrics,
+}
+
+impl Meter for Instrument {
+    type Error = Error;
+
+    const NEEDS_COST: bool = false;
+
+    fn charge(
+        &mut self,
+        _stage: Stage,
+        _cost: impl FnOnce() -> VerificationCost,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn run<T>(&mut self, name: &'static str, f: impl FnOnce() -> T) -> T {
+        tracing::debug_span!("sp1_zkv_verify", stage = name).in_scope(f)
+    }
+
+    fn spend(&mut self, stage: Stage, spent: impl FnOnce() -> StageMetrics) -> Result<(), Error> {
+        let spent = spent();
+        if spent != StageMetrics::default() {
+            self.metrics.add(stage, spent);
+        }
+        Ok(())
+    }
+}
+
+/// Verify a proof like [`crate::verify_with_backend`], emitting a tracing span for each stage and
+/// counting the operations it spends.
+pub fn verify_instrumented<H: HashBackend>(
+    backend: &H,
+    vkey_digest: &[u8; 32],
+    proof: &Proof,
+    inputs: &[u8],
+) -> (Result<(), Error>, VerificationMetrics) {
+    let mut instrument = Instrument::default();
+    let result =
+        verify_with_meter(backend, vkey_digest, proof, inputs, &mut instrument).map(|_| ());
+    (result, instrument.metrics)
+}
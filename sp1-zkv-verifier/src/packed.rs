@@ -0,0 +1,234 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A [`HashBackend`] running batches of Poseidon2 permutations over Plonky3's packed BabyBear,
+//! which uses AVX2, AVX-512 or NEON when the target enables them and falls back to scalar code
+//! otherwise.
+//!
+//! Each lane of a packed permutation runs an independent state. The shrink STARK verifier checks
+//! the trace and FRI Merkle openings of all the queries of a proof together, which makes up
+//! almost all of its Poseidon2 permutations, so [`PackedBackend`] speeds up any verification
+//! entry point. [`verify_batch`] also batches the recursion vk hashes and Merkle paths of many
+//! proofs.
+
+use crate::{
+    Error, InnerSC, Proof, RecursionVkHash,
+    hash::{
+        CountingBackend, HashBackend, NativeBackend, POSEIDON2_WIDTH, poseidon2_compress_many,
+        poseidon2_hash_many,
+    },
+    merkle::MerkleProof,
+    verifier::{check_public_values, hash_babybear_inputs, verify_shrink_proof},
+    vks::recursion_vk_root,
+};
+use alloc::vec::Vec;
+use p3_baby_bear::BabyBear;
+use p3_field::{Field, PackedValue};
+use p3_symmetric::Permutation;
+use p3_util::reverse_bits_len;
+use sp1_stark_no_std::{
+    DIGEST_SIZE, InnerPerm, MachineVerificationError, StarkVerifyingKey, inner_perm,
+};
+
+type Packed = <BabyBear as Field>::Packing;
+
+/// [`NativeBackend`] running `Packed::WIDTH` Poseidon2 permutations at a time when given several.
+#[derive(Clone)]
+pub struct PackedBackend {
+    native: NativeBackend,
+    perm: InnerPerm,
+}
+
+impl Default for PackedBackend {
+    fn default() -> Self {
+        Self {
+            native: NativeBackend::default(),
+            perm: inner_perm(),
+        }
+    }
+}
+
+impl HashBackend for PackedBackend {
+    fn sha256(&self, data: &[u8]) -> [u8; 32] {
+        self.native.sha256(data)
+    }
+
+    fn blake3(&self, data: &[u8]) -> [u8; 32] {
+        self.native.blake3(data)
+    }
+
+    fn poseidon2_permute(&self, state: &mut [BabyBear; POSEIDON2_WIDTH]) {
+        self.native.poseidon2_permute(state);
+    }
+
+    fn poseidon2_permute_many(&self, states: &mut [[BabyBear; POSEIDON2_WIDTH]]) {
+        let mut lanes = states.chunks_exact_mut(Packed::WIDTH);
+        for lanes in &mut lanes {
+            let mut packed: [Packed; POSEIDON2_WIDTH] =
+                core::array::from_fn(|i| Packed::from_fn(|lane| lanes[lane][i]));
+            self.perm.permute_mut(&mut packed);
+            for (lane, state) in lanes.iter_mut().enumerate() {
+                *state = core::array::from_fn(|i| packed[i].as_slice()[lane]);
+            }
+        }
+        lanes
+            .into_remainder()
+            .iter_mut()
+            .for_each(|state| self.native.poseidon2_permute(state));
+    }
+}
+
+/// Compute [`crate::recursion_vk_hash`] for every vk in `vks`, running the permutations of all of
+/// them together through `backend`.
+pub fn recursion_vk_hashes<'a, H: HashBackend>(
+    backend: &H,
+    vks: impl IntoIterator<Item = &'a StarkVerifyingKey<InnerSC>>,
+) -> Vec<RecursionVkHash> {
+    poseidon2_hash_many(backend, vks.into_iter().map(hash_babybear_inputs))
+}
+
+/// Check every `(proof, value, commitment)` Merkle path, running the compressions of each level of
+/// all of them together through `backend`. Return whether each path is valid, in order.
+pub fn are_merkle_paths_valid<H: HashBackend>(
+    backend: &H,
+    paths: &[(
+        &MerkleProof,
+        [BabyBear; DIGEST_SIZE],
+        [BabyBear; DIGEST_SIZE],
+    )],
+) -> Vec<bool> {
+    let mut values: Vec<_> = paths.iter().map(|(_, value, _)| *value).collect();
+    let mut indices: Vec<_> = paths
+        .iter()
+        .map(|(proof, ..)| reverse_bits_len(proof.index, proof.path.len()))
+        .collect();
+    let depth = paths.iter().map(|(proof, ..)| proof.path.len()).max();
+
+    for level in 0..depth.unwrap_or_default() {
+        // Paths of different depths are done at different levels.
+        let pending: Vec<_> = (0..paths.len())
+            .filter(|&i| level < paths[i].0.path.len())
+            .collect();
+        let pairs = pending.iter().map(|&i| {
+            let sibling = paths[i].0.path[level];
+            if indices[i] % 2 == 0 {
+                [values[i], sibling]
+            } else {
+                [sibling, values[i]]
+            }
+        });
+        let compressed = poseidon2_compress_many(backend, pairs);
+        for (&i, value) in pending.iter().zip(compressed) {
+            values[i] = value;
+            indices[i] >>= 1;
+        }
+    }
+    paths
+        .iter()
+        .zip(values)
+        .map(|((_, _, commitment), value)| value == *commitment)
+        .collect()
+}
+
+/// Verify every `(vkey_digest, proof, inputs)` like [`crate::verify_with_backend`], batching the
+/// recursion vk hashes and their Merkle paths across proofs. Everything is hashed with `backend`;
+/// pass a [`PackedBackend`] to run the batches on packed lanes.
+pub fn verify_batch<H: HashBackend>(
+    backend: &H,
+    batch: &[(&[u8; 32], &Proof, &[u8])],
+) -> Vec<Result<(), Error>> {
+    let vk_hashes = recursion_vk_hashes(backend, batch.iter().map(|(_, proof, _)| &proof.vk));
+    let paths: Vec<_> = batch
+        .iter()
+        .zip(vk_hashes)
+        .map(|((_, proof, _), vk_hash)| (&proof.vk_merkle_proof, vk_hash, recursion_vk_root()))
+        .collect();
+    let memberships = are_merkle_paths_valid(backend, &paths);
+
+    batch
+        .iter()
+        .zip(memberships)
+        .map(|((vkey_digest, proof, inputs), is_member)| {
+            let backend = &CountingBackend::new(backend);
+            let meter = &mut ();
+            check_public_values(backend, vkey_digest, &proof.shard_proof, inputs, meter)?;
+            if !is_member {
+                return Err(MachineVerificationError::InvalidVerificationKey);
+            }
+            verify_shrink_proof(backend, &proof.vk, &proof.shard_proof, meter)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merkle::merkle_root;
+    use alloc::vec;
+    use p3_field::AbstractField;
+
+    fn digest(seed: u32) -> [BabyBear; DIGEST_SIZE] {
+        core::array::from_fn(|i| BabyBear::from_canonical_u32(seed * 31 + i as u32))
+    }
+
+    #[test]
+    fn permute_like_the_native_backend() {
+        // More states than lanes, and a remainder that doesn't fill a packed permutation.
+        let mut states: Vec<[BabyBear; POSEIDON2_WIDTH]> = (0..2 * Packed::WIDTH as u32 + 3)
+            .map(|i| core::array::from_fn(|j| BabyBear::from_canonical_u32(i * 97 + j as u32)))
+            .collect();
+        let mut expected = states.clone();
+        NativeBackend::default().poseidon2_permute_many(&mut expected);
+
+        PackedBackend::default().poseidon2_permute_many(&mut states);
+        assert_eq!(states, expected);
+    }
+
+    #[test]
+    fn merkle_paths_match_scalar_path_bit_for_bit() {
+        let backend = NativeBackend::default();
+        let packed = PackedBackend::default();
+        // More paths than lanes, with different depths so that some lanes finish early.
+        let proofs: Vec<_> = (0..2 * Packed::WIDTH as u32 + 1)
+            .map(|i| MerkleProof {
+                index: i as usize % 4,
+                path: (0..2 + i % 5).map(|j| digest(i * 7 + j)).collect(),
+            })
+            .collect();
+        let values: Vec<_> = (0..proofs.len() as u32).map(|i| digest(1000 + i)).collect();
+        let roots: Vec<_> = proofs
+            .iter()
+            .zip(&values)
+            .map(|(proof, value)| merkle_root(&backend, proof, *value))
+            .collect();
+
+        let paths: Vec<_> = proofs
+            .iter()
+            .zip(&values)
+            .zip(&roots)
+            .map(|((proof, value), root)| (proof, *value, *root))
+            .collect();
+        assert_eq!(
+            are_merkle_paths_valid(&packed, &paths),
+            vec![true; paths.len()]
+        );
+
+        let mut tampered = paths.clone();
+        tampered[1].2[0] += BabyBear::one();
+        let mut expected = vec![true; paths.len()];
+        expected[1] = false;
+        assert_eq!(are_merkle_paths_valid(&packed, &tampered), expected);
+    }
+}
@@ -0,0 +1,209 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{
+    InnerSC, ShardProof,
+    hash::HashBackend,
+    merkle::{MerkleOpening, verify_batch_openings},
+};
+use alloc::{vec, vec::Vec};
+use core::ops::Range;
+use p3_baby_bear::BabyBear;
+use p3_field::{AbstractExtensionField, AbstractField, Field, TwoAdicField};
+use p3_fri::{FriError, VerificationError};
+use p3_merkle_tree::FieldMerkleTreeError;
+use p3_util::reverse_bits_len;
+use sp1_stark_no_std::StarkGenericConfig;
+
+pub(crate) type Challenge = <InnerSC as StarkGenericConfig>::Challenge;
+
+/// Why the opening argument of a shrink proof doesn't hold, as reported by the upstream PCS.
+pub(crate) type OpeningError = VerificationError<FieldMerkleTreeError, FieldMerkleTreeError>;
+
+/// Reduced openings are indexed by the log height of the matrices they come from.
+pub(crate) const MAX_LOG_HEIGHT: usize = 32;
+
+/// A committed matrix, and the values it's claimed to take at some points.
+pub(crate) struct Matrix<'a> {
+    /// Log2 of the height of the trace, before the low degree extension.
+    pub(crate) log_height: usize,
+    pub(crate) openings: Vec<(Challenge, &'a [Challenge])>,
+}
+
+/// The matrices committed to `commit`, in the order they were committed.
+pub(crate) struct Round<'a> {
+    pub(crate) commit: &'a [BabyBear],
+    pub(crate) matrices: Vec<Matrix<'a>>,
+}
+
+/// Check that every query opens exactly one batch per round.
+pub(crate) fn check_shape(
+    shard_proof: &ShardProof,
+    num_queries: usize,
+    num_rounds: usize,
+) -> Result<(), OpeningError> {
+    let query_openings = &shard_proof.opening_proof.query_openings;
+    if query_openings.len() != num_queries {
+        return Err(OpeningError::FriError(FriError::InvalidProofShape));
+    }
+    if query_openings
+        .iter()
+        .any(|batches| batches.len() != num_rounds)
+    {
+        return Err(OpeningError::InputMmcsError(
+            FieldMerkleTreeError::WrongBatchSize,
+        ));
+    }
+    Ok(())
+}
+
+/// What the trace openings of each query reduce to, per log height of the low degree extension:
+/// every value `p(x)` opened at `x` enters as `alpha^i * (p(x) - p(z)) / (x - z)`, where `p(z)` is
+/// the value claimed at the point `z`.
+pub(crate) struct ReducedOpenings {
+    alpha: Challenge,
+    queries: Range<usize>,
+    values: Vec<[Challenge; MAX_LOG_HEIGHT]>,
+    alpha_pows: Vec<[Challenge; MAX_LOG_HEIGHT]>,
+}
+
+impl ReducedOpenings {
+    /// Start reducing the openings of `queries`, out of all the queries of the proof.
+    pub(crate) fn new(alpha: Challenge, queries: Range<usize>) -> Self {
+        Self {
+            alpha,
+            values: vec![[Challenge::zero(); MAX_LOG_HEIGHT]; queries.len()],
+            alpha_pows: vec![[Challenge::one(); MAX_LOG_HEIGHT]; queries.len()],
+            queries,
+        }
+    }
+
+    /// The reduced openings of the `query`-th of the queries being reduced.
+    pub(crate) fn of_query(&self, query: usize) -> &[Challenge; MAX_LOG_HEIGHT] {
+        &self.values[query]
+    }
+
+    /// Check the openings of batch `batch`, committed in `round`, at the index of every query
+    /// being reduced, and reduce them. Returns the field operations spent.
+    ///
+    /// Together, the batches do what Plonky3's `TwoAdicFriPcs::verify` does one query at a time,
+    /// once [`check_shape`] succeeded.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn open_batch<H: HashBackend>(
+        &mut self,
+        backend: &H,
+        shard_proof: &ShardProof,
+        batch: usize,
+        round: &Round<'_>,
+        indices: &[usize],
+        log_global_max_height: usize,
+        log_blowup: usize,
+    ) -> Result<u64, OpeningError> {
+        let query_openings = &shard_proof.opening_proof.query_openings;
+        let heights: Vec<usize> = round
+            .matrices
+            .iter()
+            .map(|matrix| 1 << (matrix.log_height + log_blowup))
+            .collect();
+        let log_batch_max_height = round
+            .matrices
+            .iter()
+            .map(|matrix| matrix.log_height + log_blowup)
+            .max()
+            .ok_or(OpeningError::InputMmcsError(
+                FieldMerkleTreeError::WrongBatchSize,
+            ))?;
+        let bits_reduced = log_global_max_height
+            .checked_sub(log_batch_max_height)
+            .ok_or(OpeningError::FriError(FriError::InvalidProofShape))?;
+
+        let indices = &indices[self.queries.clone()];
+        let openings: Vec<_> = query_openings[self.queries.clone()]
+            .iter()
+            .zip(indices)
+            .map(|(batches, &index)| MerkleOpening {
+                index: index >> bits_reduced,
+                rows: &batches[batch].opened_values,
+                path: &batches[batch].opening_proof,
+            })
+            .collect();
+        verify_batch_openings(backend, round.commit, &heights, &openings)
+            .map_err(OpeningError::InputMmcsError)?;
+
+        let mut field_ops = 0;
+        for (query, (opening, &index)) in openings.iter().zip(indices).enumerate() {
+            let values = &mut self.values[query];
+            let alpha_pows = &mut self.alpha_pows[query];
+            for (row, matrix) in opening.rows.iter().zip(&round.matrices) {
+                let log_height = matrix.log_height + log_blowup;
+                let bits_reduced = log_global_max_height - log_height;
+                let rev_reduced_index = reverse_bits_len(index >> bits_reduced, log_height);
+                let x = BabyBear::generator() * two_adic_power(log_height, rev_reduced_index);
+                field_ops += 2 * log_height as u64 + 1;
+                for (z, values_at_z) in &matrix.openings {
+                    if row.len() != values_at_z.len() {
+                        return Err(OpeningError::FriError(FriError::InvalidProofShape));
+                    }
+                    let inverse = (Challenge::from_base(x) - *z)
+                        .try_inverse()
+                        .ok_or(OpeningError::FriError(FriError::InvalidProofShape))?;
+                    field_ops += 2;
+                    for (&p_at_x, &p_at_z) in row.iter().zip(*values_at_z) {
+                        let quotient = (Challenge::from_base(p_at_x) - p_at_z) * inverse;
+                        values[log_height] += alpha_pows[log_height] * quotient;
+                        alpha_pows[log_height] *= self.alpha;
+                        field_ops += 5;
+                    }
+                }
+            }
+        }
+        Ok(field_ops)
+    }
+}
+
+/// `g^exponent` for the generator `g` of the subgroup of order `2^log_order`.
+///
+/// Takes one squaring and one multiplication per bit whatever the exponent, so that every query
+/// spends the same.
+pub(crate) fn two_adic_power(log_order: usize, exponent: usize) -> BabyBear {
+    let mut power = BabyBear::two_adic_generator(log_order);
+    let mut result = BabyBear::one();
+    for bit in 0..log_order {
+        result *= if (exponent >> bit) & 1 == 1 {
+            power
+        } else {
+            BabyBear::one()
+        };
+        power = power.square();
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raise_two_adic_generators() {
+        for log_order in [0, 1, 5, 27] {
+            for exponent in [0, 1, 6, (1 << log_order) - 1] {
+                assert_eq!(
+                    two_adic_power(log_order, exponent),
+                    BabyBear::two_adic_generator(log_order).exp_u64(exponent as u64),
+                    "{log_order} {exponent}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! What a verified SP1 proof establishes, and the identifiers zkVerify gives to it.
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+
+/// Hash function an SP1 program used to commit to its public inputs.
+///
+/// The discriminants are part of the signed message of attestations, so they must never change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum HashKind {
+    Sha256 = 0,
+    Blake3 = 1,
+}
+
+/// The facts established by a successful verification, see [`crate::verify_statement`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifiedStatement {
+    /// Digest of the SP1 program vk.
+    pub vkey_digest: [u8; 32],
+    /// Digest of the public inputs committed by the program.
+    pub committed_value_digest: [u8; 32],
+    /// Hash function of `committed_value_digest`.
+    pub hash_kind: HashKind,
+    /// Root of the recursion vk Merkle tree the proof was checked against.
+    pub vk_root: [u8; 32],
+}
+
+/// Tag identifying SP1 in zkVerify statement hashes.
+pub const SP1_CONTEXT: &[u8] = b"sp1";
+
+/// The zkVerify statement hash of an SP1 proof for program `vkey_digest` with public `inputs`:
+/// `keccak256(keccak256(SP1_CONTEXT) || vkey_digest || keccak256(inputs))`.
+///
+/// The program vk digest is used as the vk hash, as zkVerify does for SP1.
+pub fn statement_hash(vkey_digest: &[u8; 32], inputs: &[u8]) -> [u8; 32] {
+    Keccak256::new()
+        .chain_update(Keccak256::digest(SP1_CONTEXT))
+        .chain_update(vkey_digest)
+        .chain_update(Keccak256::digest(inputs))
+        .finalize()
+        .into()
+}
+
+/// The leaf of the statement of an SP1 proof in a zkVerify aggregation tree, that is the Keccak
+/// hash of its [`statement_hash`].
+pub fn statement_leaf(vkey_digest: &[u8; 32], inputs: &[u8]) -> [u8; 32] {
+    Keccak256::digest(statement_hash(vkey_digest, inputs)).into()
+}
@@ -13,20 +13,55 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{Error, InnerSC, ShardProof, checks::*, merkle::*, vks::*};
+use crate::{
+    Error, InnerSC, RecursionVkHash, ShardProof,
+    cache::RecursionVkCache,
+    challenger::Challenger,
+    checks::*,
+    cost::*,
+    fri::{self, Folding},
+    hash::{CountingBackend, HashBackend, NativeBackend, poseidon2_hash},
+    merkle::*,
+    meter::{Meter, Stage},
+    pcs::{self, Challenge, Matrix, OpeningError, ReducedOpenings, Round},
+    statement::VerifiedStatement,
+    vks::*,
+};
 use alloc::{vec, vec::Vec};
-use core::{borrow::Borrow, fmt::Debug};
+use core::{borrow::Borrow, fmt::Debug, iter::once, ops::Range};
+use p3_air::BaseAir;
 use p3_baby_bear::BabyBear;
-use p3_field::{AbstractField, Field, TwoAdicField};
+use p3_commit::{PolynomialSpace, TwoAdicMultiplicativeCoset};
+use p3_field::{AbstractExtensionField, AbstractField, Field, TwoAdicField};
+use p3_fri::FriError;
 use serde::{Deserialize, Serialize};
-use sp1_primitives::poseidon2_hash;
-use sp1_recursion_core_no_std::machine::RecursionAir;
+use sp1_recursion_core_no_std::{air::RecursionPublicValues, machine::RecursionAir};
 use sp1_stark_no_std::{
-    DIGEST_SIZE, MachineProof, MachineVerificationError, StarkGenericConfig, StarkVerifyingKey,
+    AirOpenedValues, Chip, ChipOpenedValues, DIGEST_SIZE, MachineVerificationError,
+    OpeningShapeError, StarkMachine, StarkVerifyingKey, VerificationError, Verifier,
+    air::{InteractionScope, MachineAir},
+    septic_digest::SepticDigest,
 };
+use spin::Once;
 
 const SHRINK_DEGREE: usize = 3;
 type ShrinkAir<F> = RecursionAir<F, SHRINK_DEGREE>;
+type ShrinkVerifier = Verifier<InnerSC, ShrinkAir<BabyBear>>;
+pub(crate) type ShrinkChip = Chip<BabyBear, ShrinkAir<BabyBear>>;
+pub(crate) type ShrinkMachine = StarkMachine<InnerSC, ShrinkAir<BabyBear>>;
+
+/// Message of the [`Error::InvalidPublicValues`] returned when a proof is about other public
+/// inputs than the ones it's verified with.
+pub const PUBLIC_INPUTS_MISMATCH: &str = "public input hash mismatch";
+
+/// Number of recursion public values committed to by a shrink proof.
+pub const NUM_PUBLIC_VALUES: usize = core::mem::size_of::<RecursionPublicValues<u8>>();
+
+/// The machine the shrink proofs are made with, built on first use.
+pub(crate) fn shrink_machine() -> &'static ShrinkMachine {
+    static SHRINK_MACHINE: Once<ShrinkMachine> = Once::new();
+    SHRINK_MACHINE.call_once(|| ShrinkAir::shrink_machine(InnerSC::compressed()))
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Proof {
@@ -36,83 +71,762 @@ pub struct Proof {
 }
 
 pub fn verify(vkey_digest: &[u8; 32], proof: &Proof, inputs: &[u8]) -> Result<(), Error> {
+    verify_with_backend(&NativeBackend::default(), vkey_digest, proof, inputs)
+}
+
+/// Verify a proof like [`verify`], computing every hash with `backend`.
+pub fn verify_with_backend<H: HashBackend>(
+    backend: &H,
+    vkey_digest: &[u8; 32],
+    proof: &Proof,
+    inputs: &[u8],
+) -> Result<(), Error> {
+    verify_with_meter(backend, vkey_digest, proof, inputs, &mut ()).map(|_| ())
+}
+
+/// Verify a proof like [`verify_with_backend`], returning what it establishes.
+pub fn verify_statement<H: HashBackend>(
+    backend: &H,
+    vkey_digest: &[u8; 32],
+    proof: &Proof,
+    inputs: &[u8],
+) -> Result<VerifiedStatement, Error> {
+    verify_with_meter(backend, vkey_digest, proof, inputs, &mut ())
+}
+
+/// Verify a proof like [`verify_with_backend`], skipping the recursion vk Merkle membership check
+/// when the recursion vk hash is already known to `cache`. Hashes proven to be members are
+/// remembered.
+pub fn verify_cached<H: HashBackend>(
+    backend: &H,
+    vkey_digest: &[u8; 32],
+    proof: &Proof,
+    inputs: &[u8],
+    cache: &mut RecursionVkCache,
+) -> Result<(), Error> {
+    let Proof {
+        shard_proof,
+        vk: recursion_vkey,
+        vk_merkle_proof,
+    } = proof;
+
+    let backend = &CountingBackend::new(backend);
+    let meter = &mut ();
+    check_public_values(backend, vkey_digest, shard_proof, inputs, meter)?;
+    let vk_hash = recursion_vk_hash_metered(backend, recursion_vkey, meter)?;
+    if !cache.contains(&vk_hash) {
+        check_recursion_vk_membership(backend, vk_hash, vk_merkle_proof, meter)?;
+        cache.insert(vk_hash);
+    }
+    verify_shrink_proof(backend, recursion_vkey, shard_proof, meter)
+}
+
+/// Compute the hash identifying a recursion verifying key inside the recursion vk Merkle tree.
+pub fn recursion_vk_hash<H: HashBackend>(
+    backend: &H,
+    vk: &StarkVerifyingKey<InnerSC>,
+) -> RecursionVkHash {
+    hash_babybear(backend, vk)
+}
+
+/// The digest of the SP1 program vk `proof` claims to be about, which [`verify`] checks against
+/// the expected one.
+///
+/// # Panics
+///
+/// If `proof` doesn't have as many public values as a shrink proof, which [`verify`] rejects.
+pub fn claimed_vkey_digest(proof: &Proof) -> [u8; 32] {
+    let public_values: &RecursionPublicValues<BabyBear> =
+        proof.shard_proof.public_values.as_slice().borrow();
+    digest_bytes(&public_values.sp1_vk_digest)
+}
+
+pub(crate) fn verify_with_meter<H: HashBackend, M: Meter>(
+    backend: &H,
+    vkey_digest: &[u8; 32],
+    proof: &Proof,
+    inputs: &[u8],
+    meter: &mut M,
+) -> Result<VerifiedStatement, M::Error> {
     let Proof {
         shard_proof,
         vk: recursion_vkey,
         vk_merkle_proof,
     } = proof;
 
+    let backend = &CountingBackend::new(backend);
+    let statement = check_public_values(backend, vkey_digest, shard_proof, inputs, meter)?;
+    let vk_hash = recursion_vk_hash_metered(backend, recursion_vkey, meter)?;
+    check_recursion_vk_membership(backend, vk_hash, vk_merkle_proof, meter)?;
+    verify_shrink_proof(backend, recursion_vkey, shard_proof, meter)?;
+    Ok(statement)
+}
+
+/// Account for what `backend` counted since the last checkpoint, along with `field_ops`, as spent
+/// by `stage`.
+fn spend<H: HashBackend, M: Meter>(
+    meter: &mut M,
+    stage: Stage,
+    backend: &CountingBackend<'_, H>,
+    field_ops: impl FnOnce() -> u64,
+) -> Result<(), M::Error> {
+    let mut spent = backend.take();
+    meter.spend(stage, || {
+        spent.cost.field_ops += field_ops();
+        spent
+    })
+}
+
+pub(crate) fn check_public_values<H: HashBackend, M: Meter>(
+    backend: &CountingBackend<'_, H>,
+    vkey_digest: &[u8; 32],
+    shard_proof: &ShardProof,
+    inputs: &[u8],
+    meter: &mut M,
+) -> Result<VerifiedStatement, M::Error> {
+    // Viewing fewer values as recursion public values would panic.
+    if shard_proof.public_values.len() != NUM_PUBLIC_VALUES {
+        return Err(
+            MachineVerificationError::InvalidPublicValues("public values length mismatch").into(),
+        );
+    }
     let public_values = shard_proof.public_values.as_slice().borrow();
 
-    if !is_public_inputs_digest_valid(public_values, inputs) {
+    meter.charge(Stage::PublicInputsHash, || public_inputs_cost(inputs.len()))?;
+    let hash_kind = meter.run("public_inputs_hash", || {
+        public_inputs_hash_kind(backend, public_values, inputs)
+    });
+    spend(meter, Stage::PublicInputsHash, backend, || 0)?;
+    let Some(hash_kind) = hash_kind else {
+        return Err(MachineVerificationError::InvalidPublicValues(PUBLIC_INPUTS_MISMATCH).into());
+    };
+
+    meter.charge(Stage::VkDigest, VerificationCost::default)?;
+    meter.run("vk_digest", || {
+        if !is_vkey_digest_valid(public_values, vkey_digest) {
+            return Err(MachineVerificationError::InvalidPublicValues(
+                "sp1 vk hash mismatch",
+            ));
+        }
+        if public_values.vk_root != recursion_vk_root() {
+            return Err(MachineVerificationError::InvalidPublicValues(
+                "vk_root mismatch",
+            ));
+        }
+        if !public_values.is_complete.is_one() {
+            return Err(MachineVerificationError::InvalidPublicValues(
+                "is_complete is not 1",
+            ));
+        }
+        Ok(())
+    })?;
+
+    meter.charge(Stage::PublicValuesDigest, public_values_digest_cost)?;
+    let is_valid = meter.run("public_values_digest", || {
+        is_recursion_public_values_valid(backend, public_values)
+    });
+    spend(meter, Stage::PublicValuesDigest, backend, || 0)?;
+    if !is_valid {
         return Err(MachineVerificationError::InvalidPublicValues(
-            "public input hash mismatch",
-        ));
+            "recursion public values are invalid",
+        )
+        .into());
     }
-    if !is_vkey_digest_valid(public_values, vkey_digest) {
-        return Err(MachineVerificationError::InvalidPublicValues(
-            "sp1 vk hash mismatch",
-        ));
+    Ok(VerifiedStatement {
+        vkey_digest: *vkey_digest,
+        committed_value_digest: committed_value_digest_bytes(public_values),
+        hash_kind,
+        vk_root: digest_bytes(&public_values.vk_root),
+    })
+}
+
+pub(crate) fn recursion_vk_hash_metered<H: HashBackend, M: Meter>(
+    backend: &CountingBackend<'_, H>,
+    recursion_vkey: &StarkVerifyingKey<InnerSC>,
+    meter: &mut M,
+) -> Result<RecursionVkHash, M::Error> {
+    meter.charge(Stage::RecursionVkHash, || {
+        recursion_vk_hash_cost(hash_babybear_input_len(recursion_vkey))
+    })?;
+    let vk_hash = meter.run("recursion_vk_hash", || {
+        hash_babybear(backend, recursion_vkey)
+    });
+    spend(meter, Stage::RecursionVkHash, backend, || 0)?;
+    Ok(vk_hash)
+}
+
+pub(crate) fn check_recursion_vk_membership<H: HashBackend, M: Meter>(
+    backend: &CountingBackend<'_, H>,
+    vk_hash: RecursionVkHash,
+    vk_merkle_proof: &MerkleProof,
+    meter: &mut M,
+) -> Result<(), M::Error> {
+    meter.charge(Stage::MerklePath, || {
+        merkle_path_cost(vk_merkle_proof.path.len())
+    })?;
+    let is_member = meter.run("merkle_path", || {
+        is_merkle_path_valid(backend, vk_merkle_proof, vk_hash, recursion_vk_root())
+    });
+    spend(meter, Stage::MerklePath, backend, || 0)?;
+    if !is_member {
+        return Err(MachineVerificationError::InvalidVerificationKey.into());
     }
-    if public_values.vk_root != recursion_vk_root() {
-        return Err(MachineVerificationError::InvalidPublicValues(
-            "vk_root mismatch",
-        ));
+    Ok(())
+}
+
+/// Verify the shrink STARK proof like `StarkMachine::verify` does for a single shard, hashing the
+/// transcript and every Merkle opening with `backend`.
+///
+/// The trace openings are checked a batch at a time and the FRI commit phase a round at a time,
+/// each for all the queries together, or one query at a time with the `small-footprint` feature.
+/// What each of these steps spent is accounted for right after it ran.
+pub(crate) fn verify_shrink_proof<H: HashBackend, M: Meter>(
+    backend: &CountingBackend<'_, H>,
+    recursion_vkey: &StarkVerifyingKey<InnerSC>,
+    shard_proof: &ShardProof,
+    meter: &mut M,
+) -> Result<(), M::Error> {
+    if M::NEEDS_COST {
+        let shape = ProofShape::new(recursion_vkey)?;
+        meter.charge(Stage::Constraints, || shape.constraints_cost())?;
+        meter.charge(Stage::Pcs, || shape.pcs_cost())?;
+        meter.charge(Stage::Fri, || shape.fri_cost())?;
     }
-    if !public_values.is_complete.is_one() {
-        return Err(MachineVerificationError::InvalidPublicValues(
-            "is_complete is not 1",
+
+    let shrink_machine = shrink_machine();
+    let fri_params = fri::fri_params();
+    let num_public_values = shrink_machine.num_pv_elts();
+    let chips = shrink_machine
+        .shard_chips_ordered(&shard_proof.chip_ordering)
+        .collect::<Vec<_>>();
+    let mut challenger = Challenger::new(backend);
+
+    let challenges = meter.run("shrink_transcript", || {
+        observe_shard(
+            &mut challenger,
+            recursion_vkey,
+            &chips,
+            shard_proof,
+            num_public_values,
+        )
+    })?;
+    spend(meter, Stage::Pcs, backend, || 0)?;
+    let domains = opened_domains(&chips, shard_proof, fri_params.log_blowup)
+        .map_err(MachineVerificationError::InvalidShardProof)?;
+    let rounds = opening_rounds(recursion_vkey, shard_proof, &domains, challenges.zeta)
+        .map_err(MachineVerificationError::InvalidShardProof)?;
+
+    let fri_challenges = meter
+        .run("shrink_fri", || {
+            fri::sample_challenges(&mut challenger, shard_proof, fri_params)
+        })
+        .map_err(|error| opening_error(OpeningError::FriError(error)))?;
+    spend(meter, Stage::Fri, backend, || 0)?;
+
+    pcs::check_shape(shard_proof, fri_params.num_queries, rounds.len()).map_err(opening_error)?;
+    for queries in query_passes(fri_params.num_queries) {
+        let mut reduced_openings = ReducedOpenings::new(challenges.pcs_alpha, queries.clone());
+        for (batch, round) in rounds.iter().enumerate() {
+            let field_ops = meter
+                .run("shrink_pcs", || {
+                    reduced_openings.open_batch(
+                        backend,
+                        shard_proof,
+                        batch,
+                        round,
+                        &fri_challenges.query_indices,
+                        fri_challenges.log_max_height,
+                        fri_params.log_blowup,
+                    )
+                })
+                .map_err(opening_error)?;
+            spend(meter, Stage::Pcs, backend, || field_ops)?;
+        }
+
+        let (mut folding, field_ops) = meter
+            .run("shrink_fri", || {
+                Folding::new(shard_proof, &fri_challenges, queries)
+            })
+            .map_err(|error| opening_error(OpeningError::FriError(error)))?;
+        spend(meter, Stage::Fri, backend, || field_ops)?;
+        for round in 0..fri_challenges.betas.len() {
+            let field_ops = meter
+                .run("shrink_fri", || {
+                    folding.fold_round(
+                        backend,
+                        shard_proof,
+                        &fri_challenges,
+                        &reduced_openings,
+                        round,
+                    )
+                })
+                .map_err(|error| opening_error(OpeningError::FriError(error)))?;
+            spend(meter, Stage::Fri, backend, || field_ops)?;
+        }
+        meter
+            .run("shrink_fri", || folding.check_final_poly(shard_proof))
+            .map_err(|error| opening_error(OpeningError::FriError(error)))?;
+    }
+
+    for ((chip, opened), domains) in chips
+        .iter()
+        .zip(&shard_proof.opened_values.chips)
+        .zip(&domains)
+    {
+        meter
+            .run("shrink_constraints", || {
+                check_constraints(
+                    chip,
+                    opened,
+                    domains,
+                    &challenges,
+                    &shard_proof.public_values,
+                )
+            })
+            .map_err(MachineVerificationError::InvalidShardProof)?;
+        spend(meter, Stage::Constraints, backend, || {
+            chip_constraints_field_ops(chip, opened.log_degree)
+        })?;
+    }
+
+    meter.run("shrink_cumulative_sum", || {
+        if !shard_proof.local_cumulative_sum().is_zero() {
+            return Err(MachineVerificationError::InvalidShardProof(
+                VerificationError::CumulativeSumsError("local cumulative sum is not zero"),
+            ));
+        }
+        let global_cumulative_sum: SepticDigest<BabyBear> = [
+            shard_proof.global_cumulative_sum(),
+            recursion_vkey.initial_global_cumulative_sum,
+        ]
+        .into_iter()
+        .sum();
+        if !global_cumulative_sum.is_zero() {
+            return Err(MachineVerificationError::NonZeroCumulativeSum(
+                InteractionScope::Global,
+                0,
+            ));
+        }
+        Ok(())
+    })?;
+    Ok(())
+}
+
+/// How many queries the opening argument checks at once. Checking all of them together batches
+/// their hashing, while the small-footprint mode keeps a single query in memory at a time.
+const QUERIES_PER_PASS: usize = if cfg!(feature = "small-footprint") {
+    1
+} else {
+    usize::MAX
+};
+
+/// Split the `num_queries` queries of the opening argument into passes of [`QUERIES_PER_PASS`].
+fn query_passes(num_queries: usize) -> impl Iterator<Item = Range<usize>> {
+    (0..num_queries)
+        .step_by(QUERIES_PER_PASS)
+        .map(move |start| start..start.saturating_add(QUERIES_PER_PASS).min(num_queries))
+}
+
+fn opening_error(error: OpeningError) -> Error {
+    MachineVerificationError::InvalidShardProof(VerificationError::InvalidopeningArgument(error))
+}
+
+/// What the transcript of a shard draws before its opening argument.
+struct ShardChallenges {
+    permutation: [Challenge; 2],
+    alpha: Challenge,
+    zeta: Challenge,
+    /// Combines the openings of every matrix in the PCS.
+    pcs_alpha: Challenge,
+}
+
+/// The domains a chip's traces and quotient chunks are committed over.
+struct ChipDomains {
+    trace: TwoAdicMultiplicativeCoset<BabyBear>,
+    quotient_chunks: Vec<TwoAdicMultiplicativeCoset<BabyBear>>,
+}
+
+/// Run the transcript of the shard up to its opening argument, like `StarkMachine::verify` and
+/// then `Verifier::verify_shard` do, and check the cumulative sums each chip is allowed to have.
+fn observe_shard<H: HashBackend>(
+    challenger: &mut Challenger<'_, H>,
+    vk: &StarkVerifyingKey<InnerSC>,
+    chips: &[&ShrinkChip],
+    shard_proof: &ShardProof,
+    num_public_values: usize,
+) -> Result<ShardChallenges, Error> {
+    let opened_chips = &shard_proof.opened_values.chips;
+    if chips.len() != opened_chips.len() {
+        return Err(MachineVerificationError::InvalidShardProof(
+            VerificationError::ChipOpeningLengthMismatch,
         ));
     }
-    if !is_recursion_public_values_valid(&InnerSC::default(), public_values) {
-        return Err(MachineVerificationError::InvalidPublicValues(
-            "recursion public values are invalid",
+    let public_values = shard_proof.public_values.get(..num_public_values).ok_or(
+        MachineVerificationError::InvalidPublicValues("too few public values"),
+    )?;
+
+    challenger.observe_slice(vk.commit.as_ref());
+    challenger.observe(vk.pc_start);
+    challenger.observe_slice(&vk.initial_global_cumulative_sum.0.x.0);
+    challenger.observe_slice(&vk.initial_global_cumulative_sum.0.y.0);
+    // The vk is padded to a multiple of the rate.
+    challenger.observe(BabyBear::zero());
+    challenger.observe_slice(shard_proof.commitment.main_commit.as_ref());
+    challenger.observe_slice(public_values);
+
+    let permutation = [challenger.sample_ext(), challenger.sample_ext()];
+    challenger.observe_slice(shard_proof.commitment.permutation_commit.as_ref());
+    for (chip, opened) in chips.iter().zip(opened_chips) {
+        let local_sum = opened.local_cumulative_sum;
+        let global_sum = opened.global_cumulative_sum;
+        challenger.observe_ext(local_sum);
+        challenger.observe_slice(&global_sum.0.x.0);
+        challenger.observe_slice(&global_sum.0.y.0);
+
+        if chip.commit_scope() == InteractionScope::Local && !global_sum.is_zero() {
+            return Err(MachineVerificationError::InvalidShardProof(
+                VerificationError::CumulativeSumsError(
+                    "global cumulative sum is non-zero, but chip is Local",
+                ),
+            ));
+        }
+        let has_local_interactions = chip
+            .sends()
+            .iter()
+            .chain(chip.receives())
+            .any(|interaction| interaction.scope == InteractionScope::Local);
+        if !has_local_interactions && !local_sum.is_zero() {
+            return Err(MachineVerificationError::InvalidShardProof(
+                VerificationError::CumulativeSumsError(
+                    "local cumulative sum is non-zero, but no local interactions",
+                ),
+            ));
+        }
+    }
+
+    let alpha = challenger.sample_ext();
+    challenger.observe_slice(shard_proof.commitment.quotient_commit.as_ref());
+    let zeta = challenger.sample_ext();
+    let pcs_alpha = challenger.sample_ext();
+    Ok(ShardChallenges {
+        permutation,
+        alpha,
+        zeta,
+        pcs_alpha,
+    })
+}
+
+/// Check that each chip is opened with the widths of its traces, on domains that fit in the
+/// two-adic subgroup once extended, and return these domains.
+fn opened_domains(
+    chips: &[&ShrinkChip],
+    shard_proof: &ShardProof,
+    log_blowup: usize,
+) -> Result<Vec<ChipDomains>, VerificationError<InnerSC>> {
+    chips
+        .iter()
+        .zip(&shard_proof.opened_values.chips)
+        .map(|(chip, opened)| {
+            check_opening_shape(chip, opened)
+                .map_err(|error| VerificationError::OpeningShapeError(chip.name(), error))?;
+            let log_quotient_degree = chip.log_quotient_degree();
+            if opened.log_degree
+                > BabyBear::TWO_ADICITY.saturating_sub(log_quotient_degree + log_blowup)
+            {
+                return Err(VerificationError::InvalidopeningArgument(
+                    OpeningError::FriError(FriError::InvalidProofShape),
+                ));
+            }
+            let trace = TwoAdicMultiplicativeCoset {
+                log_n: opened.log_degree,
+                shift: BabyBear::one(),
+            };
+            let quotient_chunks = trace
+                .create_disjoint_domain(1 << (opened.log_degree + log_quotient_degree))
+                .split_domains(1 << log_quotient_degree);
+            Ok(ChipDomains {
+                trace,
+                quotient_chunks,
+            })
+        })
+        .collect()
+}
+
+/// Same checks as `Verifier::verify_opening_shape`.
+fn check_opening_shape(
+    chip: &ShrinkChip,
+    opened: &ChipOpenedValues<BabyBear, Challenge>,
+) -> Result<(), OpeningShapeError> {
+    let preprocessed_width = chip.preprocessed_width();
+    for values in [&opened.preprocessed.local, &opened.preprocessed.next] {
+        if values.len() != preprocessed_width {
+            return Err(OpeningShapeError::PreprocessedWidthMismatch(
+                preprocessed_width,
+                values.len(),
+            ));
+        }
+    }
+    for values in [&opened.main.local, &opened.main.next] {
+        if values.len() != chip.width() {
+            return Err(OpeningShapeError::MainWidthMismatch(
+                chip.width(),
+                values.len(),
+            ));
+        }
+    }
+    let permutation_width = chip.permutation_width() * Challenge::D;
+    for values in [&opened.permutation.local, &opened.permutation.next] {
+        if values.len() != permutation_width {
+            return Err(OpeningShapeError::PermutationWidthMismatch(
+                permutation_width,
+                values.len(),
+            ));
+        }
+    }
+    if opened.quotient.len() != chip.quotient_width() {
+        return Err(OpeningShapeError::QuotientWidthMismatch(
+            chip.quotient_width(),
+            opened.quotient.len(),
         ));
     }
-    if !is_merkle_path_valid(
-        vk_merkle_proof,
-        hash_babybear(recursion_vkey),
-        recursion_vk_root(),
-    ) {
-        return Err(MachineVerificationError::InvalidVerificationKey);
+    for chunk in &opened.quotient {
+        if chunk.len() != Challenge::D {
+            return Err(OpeningShapeError::QuotientChunkSizeMismatch(
+                Challenge::D,
+                chunk.len(),
+            ));
+        }
     }
+    Ok(())
+}
 
-    let shrink_machine = ShrinkAir::shrink_machine(InnerSC::compressed());
-    let mut challenger = shrink_machine.config().challenger();
+/// The matrices committed in each round, and where the PCS checks them: the preprocessed traces
+/// in the order of the vk, then the main and permutation traces and the quotient chunks in the
+/// order of the shard.
+fn opening_rounds<'a>(
+    vk: &'a StarkVerifyingKey<InnerSC>,
+    shard_proof: &'a ShardProof,
+    domains: &[ChipDomains],
+    zeta: Challenge,
+) -> Result<Vec<Round<'a>>, VerificationError<InnerSC>> {
+    let opened_chips = &shard_proof.opened_values.chips;
+    let local_and_next = |log_height: usize, values: &'a AirOpenedValues<Challenge>| Matrix {
+        log_height,
+        openings: vec![
+            (zeta, values.local.as_slice()),
+            (
+                zeta * Challenge::from_base(BabyBear::two_adic_generator(log_height)),
+                values.next.as_slice(),
+            ),
+        ],
+    };
 
-    shrink_machine.verify(
-        recursion_vkey,
-        &MachineProof {
-            shard_proofs: vec![shard_proof.clone()],
+    let preprocessed = vk
+        .chip_information
+        .iter()
+        .map(|(name, domain, _)| {
+            let opened = vk
+                .chip_ordering
+                .get(name)
+                .and_then(|&index| opened_chips.get(index))
+                .ok_or(VerificationError::ChipOpeningLengthMismatch)?;
+            Ok(local_and_next(domain.log_n, &opened.preprocessed))
+        })
+        .collect::<Result<_, VerificationError<InnerSC>>>()?;
+    let main = domains
+        .iter()
+        .zip(opened_chips)
+        .map(|(domains, opened)| local_and_next(domains.trace.log_n, &opened.main))
+        .collect();
+    let permutation = domains
+        .iter()
+        .zip(opened_chips)
+        .map(|(domains, opened)| local_and_next(domains.trace.log_n, &opened.permutation))
+        .collect();
+    let quotient = domains
+        .iter()
+        .zip(opened_chips)
+        .flat_map(|(domains, opened)| domains.quotient_chunks.iter().zip(&opened.quotient))
+        .map(|(domain, chunk)| Matrix {
+            log_height: domain.log_n,
+            openings: vec![(zeta, chunk.as_slice())],
+        })
+        .collect();
+
+    let commitment = &shard_proof.commitment;
+    Ok(vec![
+        Round {
+            commit: vk.commit.as_ref(),
+            matrices: preprocessed,
         },
-        &mut challenger,
-    )
+        Round {
+            commit: commitment.main_commit.as_ref(),
+            matrices: main,
+        },
+        Round {
+            commit: commitment.permutation_commit.as_ref(),
+            matrices: permutation,
+        },
+        Round {
+            commit: commitment.quotient_commit.as_ref(),
+            matrices: quotient,
+        },
+    ])
 }
 
-fn hash_babybear(vk: &StarkVerifyingKey<InnerSC>) -> [BabyBear; DIGEST_SIZE] {
+/// Check that the constraints of `chip` at zeta match its quotient there, like
+/// `Verifier::verify_shard` does once the openings hold.
+fn check_constraints(
+    chip: &ShrinkChip,
+    opened: &ChipOpenedValues<BabyBear, Challenge>,
+    domains: &ChipDomains,
+    challenges: &ShardChallenges,
+    public_values: &[BabyBear],
+) -> Result<(), VerificationError<InnerSC>> {
+    let selectors = domains.trace.selectors_at_point(challenges.zeta);
+    let quotient =
+        ShrinkVerifier::recompute_quotient(opened, &domains.quotient_chunks, challenges.zeta);
+    let folded_constraints = ShrinkVerifier::eval_constraints(
+        chip,
+        opened,
+        &selectors,
+        challenges.alpha,
+        &challenges.permutation,
+        public_values,
+    );
+    if folded_constraints * selectors.inv_zeroifier == quotient {
+        Ok(())
+    } else {
+        Err(VerificationError::OodEvaluationMismatch(chip.name()))
+    }
+}
+
+/// Number of field elements absorbed by [`hash_babybear`] for `vk`.
+pub(crate) fn hash_babybear_input_len(vk: &StarkVerifyingKey<InnerSC>) -> usize {
     let mut num_inputs = DIGEST_SIZE + 1 + 14 + (7 * vk.chip_information.len());
     for (name, _, _) in vk.chip_information.iter() {
         num_inputs += name.len();
     }
-    let mut inputs = Vec::with_capacity(num_inputs);
-    inputs.extend(vk.commit.as_ref());
-    inputs.push(vk.pc_start);
-    inputs.extend(vk.initial_global_cumulative_sum.0.x.0);
-    inputs.extend(vk.initial_global_cumulative_sum.0.y.0);
-    for (name, domain, dimension) in vk.chip_information.iter() {
-        inputs.push(BabyBear::from_canonical_usize(domain.log_n));
-        let size = 1 << domain.log_n;
-        inputs.push(BabyBear::from_canonical_usize(size));
-        let g = BabyBear::two_adic_generator(domain.log_n);
-        inputs.push(domain.shift);
-        inputs.push(g);
-        inputs.push(BabyBear::from_canonical_usize(dimension.width));
-        inputs.push(BabyBear::from_canonical_usize(dimension.height));
-        inputs.push(BabyBear::from_canonical_usize(name.len()));
-        for byte in name.as_bytes() {
-            inputs.push(BabyBear::from_canonical_u8(*byte));
-        }
+    num_inputs
+}
+
+pub(crate) fn hash_babybear<H: HashBackend>(
+    backend: &H,
+    vk: &StarkVerifyingKey<InnerSC>,
+) -> RecursionVkHash {
+    poseidon2_hash(backend, hash_babybear_inputs(vk))
+}
+
+/// The field elements absorbed by [`hash_babybear`], streamed rather than collected since the chip
+/// names alone can take a few hundred elements.
+pub(crate) fn hash_babybear_inputs(
+    vk: &StarkVerifyingKey<InnerSC>,
+) -> impl Iterator<Item = BabyBear> + '_ {
+    let header = vk
+        .commit
+        .as_ref()
+        .iter()
+        .copied()
+        .chain(once(vk.pc_start))
+        .chain(vk.initial_global_cumulative_sum.0.x.0)
+        .chain(vk.initial_global_cumulative_sum.0.y.0);
+    let chips = vk
+        .chip_information
+        .iter()
+        .flat_map(|(name, domain, dimension)| {
+            let size = 1 << domain.log_n;
+            [
+                BabyBear::from_canonical_usize(domain.log_n),
+                BabyBear::from_canonical_usize(size),
+                domain.shift,
+                BabyBear::two_adic_generator(domain.log_n),
+                BabyBear::from_canonical_usize(dimension.width),
+                BabyBear::from_canonical_usize(dimension.height),
+                BabyBear::from_canonical_usize(name.len()),
+            ]
+            .into_iter()
+            .chain(name.bytes().map(BabyBear::from_canonical_u8))
+        });
+    header.chain(chips)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use sp1_stark_no_std::{MachineProof, StarkGenericConfig};
+    use std::fs::File;
+
+    fn valid_proof() -> Proof {
+        bincode::serde::decode_from_std_read(
+            &mut File::open("resources/proof.bin").unwrap(),
+            bincode::config::legacy(),
+        )
+        .unwrap()
     }
 
-    poseidon2_hash(inputs)
+    /// What `StarkMachine::verify` says about a machine proof made of `shard_proof` alone.
+    fn upstream_verify(
+        vk: &StarkVerifyingKey<InnerSC>,
+        shard_proof: &ShardProof,
+    ) -> Result<(), Error> {
+        let machine = shrink_machine();
+        let mut challenger = machine.config().challenger();
+        let proof = MachineProof {
+            shard_proofs: vec![shard_proof.clone()],
+        };
+        machine.verify(vk, &proof, &mut challenger)
+    }
+
+    fn tampered(tamper: impl FnOnce(&mut ShardProof)) -> Proof {
+        let mut proof = valid_proof();
+        tamper(&mut proof.shard_proof);
+        proof
+    }
+
+    #[test]
+    fn verify_shrink_proofs_like_upstream_machine() {
+        let proofs = [
+            ("valid", valid_proof()),
+            (
+                "fri sibling",
+                tampered(|proof| {
+                    proof.opening_proof.fri_proof.query_proofs[0].commit_phase_openings[0]
+                        .sibling_value = Default::default();
+                }),
+            ),
+            (
+                "public values",
+                tampered(|proof| proof.public_values[0] += BabyBear::one()),
+            ),
+            (
+                "main commitment",
+                tampered(|proof| {
+                    proof.commitment.main_commit = [BabyBear::zero(); DIGEST_SIZE].into()
+                }),
+            ),
+            (
+                "opened values",
+                tampered(|proof| proof.opened_values.chips[0].main.local[0] += BabyBear::one()),
+            ),
+            (
+                "global cumulative sum",
+                tampered(|proof| {
+                    proof.opened_values.chips[0].global_cumulative_sum = SepticDigest::zero();
+                }),
+            ),
+        ];
+        for (name, proof) in proofs {
+            let backend = NativeBackend::default();
+            let copy = verify_shrink_proof(
+                &CountingBackend::new(&backend),
+                &proof.vk,
+                &proof.shard_proof,
+                &mut (),
+            );
+            let upstream = upstream_verify(&proof.vk, &proof.shard_proof);
+            assert_eq!(copy.is_ok(), name == "valid", "{name}: {copy:?}");
+            assert_eq!(format!("{copy:?}"), format!("{upstream:?}"), "{name}");
+        }
+    }
 }
@@ -0,0 +1,92 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Measures the heap used by a verification. This lives in its own test binary, with a single
+//! test, because it installs a global allocator and needs no other test running concurrently.
+
+use sp1_zkv_verifier::MAX_VERIFY_HEAP;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fs::File,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+struct PeakAllocator;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            let current = CURRENT.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            PEAK.fetch_max(current, Ordering::SeqCst);
+            ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        CURRENT.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAllocator = PeakAllocator;
+
+#[test]
+fn verification_heap_usage_is_bounded() {
+    let proof: sp1_zkv_verifier::Proof = bincode::serde::decode_from_std_read(
+        &mut File::open("resources/proof.bin").unwrap(),
+        bincode::config::legacy(),
+    )
+    .unwrap();
+    let vk_hash: [u8; 32] =
+        hex::decode("45946758049372c74bceb6ba6526a9661b1915a3403125e470d5ff7f6c15c0dd")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let inputs =
+        hex::decode("b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9").unwrap();
+
+    // Warm up once so that lazily initialized statics don't count towards the measure.
+    sp1_zkv_verifier::verify(&vk_hash, &proof, &inputs).unwrap();
+
+    let baseline = CURRENT.load(Ordering::SeqCst);
+    PEAK.store(baseline, Ordering::SeqCst);
+    ALLOCATIONS.store(0, Ordering::SeqCst);
+
+    sp1_zkv_verifier::verify(&vk_hash, &proof, &inputs).unwrap();
+
+    let peak = PEAK.load(Ordering::SeqCst) - baseline;
+    let allocations = ALLOCATIONS.load(Ordering::SeqCst);
+    // Shown with `--nocapture`, to keep an eye on how much room the bound leaves.
+    println!(
+        "verify used {peak} bytes of heap at peak in {allocations} allocations, out of \
+         MAX_VERIFY_HEAP = {MAX_VERIFY_HEAP} bytes"
+    );
+    assert!(
+        peak <= MAX_VERIFY_HEAP,
+        "verify used {peak} bytes of heap, more than MAX_VERIFY_HEAP ({MAX_VERIFY_HEAP} bytes)"
+    );
+    assert_eq!(
+        CURRENT.load(Ordering::SeqCst),
+        baseline,
+        "verify leaked memory"
+    );
+}
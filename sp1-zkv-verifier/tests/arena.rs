@@ -0,0 +1,139 @@
+// Copyright 2025, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "arena")]
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fs::File,
+    mem::MaybeUninit,
+    ptr::addr_of_mut,
+};
+
+use sp1_zkv_verifier::{
+    Arena, ArenaError, MAX_VERIFY_HEAP, MAX_VERIFY_STACK, NativeBackend, Proof, verify_in_arena,
+};
+
+/// Leaves some room for fragmentation and for whatever the test harness allocates meanwhile.
+const ARENA_SIZE: usize = 2 * MAX_VERIFY_HEAP;
+
+static mut REGION: [MaybeUninit<u8>; ARENA_SIZE] = [MaybeUninit::uninit(); ARENA_SIZE];
+static ARENA: Arena = Arena::empty();
+
+thread_local! {
+    /// Set on the thread verifying in [`ARENA`], so that the tests running concurrently on other
+    /// threads keep allocating from the system.
+    static USE_ARENA: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Serves allocations from [`ARENA`] on threads that set [`USE_ARENA`], and from the system
+/// otherwise, since the harness allocates before any test gets the chance to initialize the
+/// arena.
+struct Routed;
+
+unsafe impl GlobalAlloc for Routed {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if USE_ARENA.try_with(Cell::get).unwrap_or(false) {
+            unsafe { ARENA.alloc(layout) }
+        } else {
+            unsafe { System.alloc(layout) }
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if ARENA.contains(ptr) {
+            unsafe { ARENA.dealloc(ptr, layout) }
+        } else {
+            unsafe { System.dealloc(ptr, layout) }
+        }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Routed = Routed;
+
+fn load_fixtures() -> (Proof, [u8; 32], Vec<u8>) {
+    let proof = bincode::serde::decode_from_std_read(
+        &mut File::open("resources/proof.bin").unwrap(),
+        bincode::config::legacy(),
+    )
+    .unwrap();
+    let vk_hash = hex::decode("45946758049372c74bceb6ba6526a9661b1915a3403125e470d5ff7f6c15c0dd")
+        .unwrap()
+        .try_into()
+        .unwrap();
+    let inputs =
+        hex::decode("b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9").unwrap();
+    (proof, vk_hash, inputs)
+}
+
+#[test]
+fn verification_fits_in_arena_and_stack_bounds() {
+    let (proof, vk_hash, inputs) = load_fixtures();
+
+    // An arena that has not been given any memory is always too small.
+    assert!(matches!(
+        verify_in_arena(&ARENA, &NativeBackend::default(), &vk_hash, &proof, &inputs),
+        Err(ArenaError::Exhausted { available: 0, .. })
+    ));
+
+    ARENA.init(unsafe { &mut *addr_of_mut!(REGION) });
+    // The arena only serves the allocations of threads that set `USE_ARENA`.
+    assert!(matches!(
+        verify_in_arena(&ARENA, &NativeBackend::default(), &vk_hash, &proof, &inputs),
+        Err(ArenaError::NotGlobalAllocator)
+    ));
+
+    let result = std::thread::Builder::new()
+        .stack_size(MAX_VERIFY_STACK)
+        .spawn(move || {
+            USE_ARENA.set(true);
+            let result =
+                verify_in_arena(&ARENA, &NativeBackend::default(), &vk_hash, &proof, &inputs);
+            USE_ARENA.set(false);
+            drop(proof);
+            result
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+    assert!(result.is_ok());
+}
+
+#[test]
+fn serve_the_reserve_once_the_arena_is_full() {
+    const SMALL_SIZE: usize = MAX_VERIFY_HEAP / 4;
+    static mut SMALL_REGION: [MaybeUninit<u8>; SMALL_SIZE] = [MaybeUninit::uninit(); SMALL_SIZE];
+    static SMALL: Arena = Arena::empty();
+    SMALL.init(unsafe { &mut *addr_of_mut!(SMALL_REGION) });
+
+    let layout = Layout::from_size_align(SMALL_SIZE / 32, 8).unwrap();
+    let mut allocations = Vec::new();
+    while !SMALL.take_overflowed() {
+        assert!(allocations.len() < 32, "the arena never used its reserve");
+        let ptr = unsafe { SMALL.alloc(layout) };
+        assert!(!ptr.is_null());
+        assert!(SMALL.contains(ptr));
+        allocations.push(ptr);
+    }
+    assert!(SMALL.free() < layout.size());
+
+    for ptr in allocations {
+        unsafe { SMALL.dealloc(ptr, layout) };
+    }
+    assert_eq!(SMALL.used(), 0);
+    assert!(!SMALL.take_overflowed());
+}
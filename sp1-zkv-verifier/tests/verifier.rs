@@ -18,7 +18,7 @@ use p3_baby_bear::BabyBear;
 use p3_field::AbstractField;
 use rstest::{fixture, rstest};
 use sp1_recursion_core_no_std::air::RecursionPublicValues;
-use sp1_zkv_verifier::Proof;
+use sp1_zkv_verifier::{NativeBackend, Proof};
 use std::fs::File;
 
 #[fixture]
@@ -111,6 +111,18 @@ mod verifier_should_accept_if {
     }
 }
 
+mod public_values_should {
+    use super::*;
+
+    #[rstest]
+    fn be_as_many_as_sp1_shrink_proofs_commit_to(valid_proof: Proof) {
+        assert_eq!(
+            valid_proof.shard_proof.public_values.len(),
+            sp1_zkv_verifier::NUM_PUBLIC_VALUES
+        );
+    }
+}
+
 mod verifier_should_reject_if {
     use super::*;
 
@@ -190,4 +202,929 @@ mod verifier_should_reject_if {
     fn inputs_are_invalid(valid_proof: Proof, valid_vk_hash: [u8; 32], invalid_inputs: Vec<u8>) {
         assert!(sp1_zkv_verifier::verify(&valid_vk_hash, &valid_proof, &invalid_inputs).is_err());
     }
+
+    #[rstest]
+    fn proof_has_too_few_public_values(
+        mut valid_proof: Proof,
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+    ) {
+        valid_proof.shard_proof.public_values.pop();
+        assert!(matches!(
+            sp1_zkv_verifier::verify(&valid_vk_hash, &valid_proof, &valid_inputs),
+            Err(sp1_zkv_verifier::Error::InvalidPublicValues(_))
+        ));
+    }
+}
+
+mod cached_verifier_should {
+    use super::*;
+    use sp1_zkv_verifier::{RecursionVkCache, recursion_vk_hash, verify_cached};
+
+    #[rstest]
+    fn remember_proven_recursion_vk(
+        valid_proof: Proof,
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+    ) {
+        let mut cache = RecursionVkCache::new(4);
+        assert!(
+            verify_cached(
+                &NativeBackend::default(),
+                &valid_vk_hash,
+                &valid_proof,
+                &valid_inputs,
+                &mut cache
+            )
+            .is_ok()
+        );
+        assert_eq!(cache.len(), 1);
+        assert!(cache.contains(&recursion_vk_hash(
+            &NativeBackend::default(),
+            &valid_proof.vk
+        )));
+    }
+
+    #[rstest]
+    fn skip_membership_check_for_cached_recursion_vk(
+        valid_proof: Proof,
+        proof_with_invalid_recursion_vk_merkle_proof: Proof,
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+    ) {
+        let mut cache = RecursionVkCache::new(4);
+        verify_cached(
+            &NativeBackend::default(),
+            &valid_vk_hash,
+            &valid_proof,
+            &valid_inputs,
+            &mut cache,
+        )
+        .unwrap();
+        assert!(
+            verify_cached(
+                &NativeBackend::default(),
+                &valid_vk_hash,
+                &proof_with_invalid_recursion_vk_merkle_proof,
+                &valid_inputs,
+                &mut cache
+            )
+            .is_ok()
+        );
+    }
+
+    #[rstest]
+    fn skip_membership_check_for_trusted_recursion_vk(
+        proof_with_invalid_recursion_vk_merkle_proof: Proof,
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+    ) {
+        let vk_hash = recursion_vk_hash(
+            &NativeBackend::default(),
+            &proof_with_invalid_recursion_vk_merkle_proof.vk,
+        );
+        let mut cache = RecursionVkCache::with_trusted(0, [vk_hash]);
+        assert!(
+            verify_cached(
+                &NativeBackend::default(),
+                &valid_vk_hash,
+                &proof_with_invalid_recursion_vk_merkle_proof,
+                &valid_inputs,
+                &mut cache
+            )
+            .is_ok()
+        );
+        assert!(cache.is_empty());
+    }
+
+    #[rstest]
+    fn reject_unknown_recursion_vk_with_invalid_merkle_proof(
+        proof_with_invalid_recursion_vk_merkle_proof: Proof,
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+    ) {
+        let mut cache = RecursionVkCache::new(4);
+        assert!(
+            verify_cached(
+                &NativeBackend::default(),
+                &valid_vk_hash,
+                &proof_with_invalid_recursion_vk_merkle_proof,
+                &valid_inputs,
+                &mut cache
+            )
+            .is_err()
+        );
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn never_count_trusted_recursion_vks_towards_capacity() {
+        let hashes: Vec<_> = (0..3u32)
+            .map(|i| [BabyBear::from_canonical_u32(i); 8])
+            .collect();
+        let cache = RecursionVkCache::with_trusted(1, hashes.clone());
+        assert!(hashes.iter().all(|h| cache.contains(h)));
+        assert!(cache.is_empty());
+    }
+}
+
+mod detached_verifier_should {
+    use super::*;
+    use sp1_zkv_verifier::{RegisteredRecursionVk, verify_detached};
+
+    #[rstest]
+    fn accept_valid_detached_proof(
+        valid_proof: Proof,
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+    ) {
+        let (proof, vk, vk_merkle_proof) = valid_proof.detach();
+        let registered_vk =
+            RegisteredRecursionVk::register(&NativeBackend::default(), vk, &vk_merkle_proof)
+                .unwrap();
+        assert!(
+            verify_detached(
+                &NativeBackend::default(),
+                &registered_vk,
+                &valid_vk_hash,
+                &proof,
+                &valid_inputs
+            )
+            .is_ok()
+        );
+    }
+
+    #[rstest]
+    fn reject_detached_proof_with_invalid_inputs(
+        valid_proof: Proof,
+        valid_vk_hash: [u8; 32],
+        invalid_inputs: Vec<u8>,
+    ) {
+        let (proof, vk, vk_merkle_proof) = valid_proof.detach();
+        let registered_vk =
+            RegisteredRecursionVk::register(&NativeBackend::default(), vk, &vk_merkle_proof)
+                .unwrap();
+        assert!(
+            verify_detached(
+                &NativeBackend::default(),
+                &registered_vk,
+                &valid_vk_hash,
+                &proof,
+                &invalid_inputs
+            )
+            .is_err()
+        );
+    }
+
+    #[rstest]
+    fn reject_invalid_detached_proof(
+        proof_with_invalid_fri_proof: Proof,
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+    ) {
+        let (proof, vk, vk_merkle_proof) = proof_with_invalid_fri_proof.detach();
+        let registered_vk =
+            RegisteredRecursionVk::register(&NativeBackend::default(), vk, &vk_merkle_proof)
+                .unwrap();
+        assert!(
+            verify_detached(
+                &NativeBackend::default(),
+                &registered_vk,
+                &valid_vk_hash,
+                &proof,
+                &valid_inputs
+            )
+            .is_err()
+        );
+    }
+
+    #[rstest]
+    fn refuse_to_register_recursion_vk_with_invalid_merkle_proof(
+        proof_with_invalid_recursion_vk_merkle_proof: Proof,
+    ) {
+        let (_, vk, vk_merkle_proof) = proof_with_invalid_recursion_vk_merkle_proof.detach();
+        assert!(
+            RegisteredRecursionVk::register(&NativeBackend::default(), vk, &vk_merkle_proof)
+                .is_err()
+        );
+    }
+
+    #[rstest]
+    fn keep_registered_recursion_vk_across_serialization(
+        valid_proof: Proof,
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+    ) {
+        let (proof, vk, vk_merkle_proof) = valid_proof.detach();
+        let registered_vk =
+            RegisteredRecursionVk::register(&NativeBackend::default(), vk, &vk_merkle_proof)
+                .unwrap();
+        let bytes =
+            bincode::serde::encode_to_vec(&registered_vk, bincode::config::legacy()).unwrap();
+        let (registered_vk, _): (RegisteredRecursionVk, _) =
+            bincode::serde::decode_from_slice(&bytes, bincode::config::legacy()).unwrap();
+        assert!(
+            verify_detached(
+                &NativeBackend::default(),
+                &registered_vk,
+                &valid_vk_hash,
+                &proof,
+                &valid_inputs
+            )
+            .is_ok()
+        );
+    }
+
+    #[rstest]
+    fn serialize_registered_recursion_vk_as_vk_and_hash(valid_proof: Proof) {
+        let (_, vk, vk_merkle_proof) = valid_proof.detach();
+        let registered_vk =
+            RegisteredRecursionVk::register(&NativeBackend::default(), vk, &vk_merkle_proof)
+                .unwrap();
+
+        // No Merkle proof is stored, so that loading the vk doesn't check its membership again.
+        assert_eq!(
+            bincode::serde::encode_to_vec(&registered_vk, bincode::config::legacy()).unwrap(),
+            bincode::serde::encode_to_vec(
+                (registered_vk.vk(), registered_vk.hash()),
+                bincode::config::legacy()
+            )
+            .unwrap()
+        );
+    }
+}
+
+mod cost_estimate_should {
+    use super::*;
+    use sp1_zkv_verifier::estimate_cost;
+
+    #[rstest]
+    fn count_input_hashes(valid_proof: Proof, valid_inputs: Vec<u8>) {
+        let cost = estimate_cost(&valid_proof, valid_inputs.len()).unwrap();
+        assert_eq!(cost.sha256_blocks, 1);
+        assert_eq!(cost.blake3_chunks, 1);
+
+        let cost = estimate_cost(&valid_proof, 2048).unwrap();
+        assert_eq!(cost.sha256_blocks, 33);
+        assert_eq!(cost.blake3_chunks, 2);
+    }
+}
+
+#[cfg(feature = "metrics")]
+mod instrumented_verifier_should {
+    use super::*;
+    use sp1_zkv_verifier::{Stage, estimate_cost, verify_instrumented};
+
+    #[rstest]
+    fn count_every_stage_of_a_valid_proof(
+        valid_proof: Proof,
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+    ) {
+        let (result, metrics) = verify_instrumented(
+            &NativeBackend::default(),
+            &valid_vk_hash,
+            &valid_proof,
+            &valid_inputs,
+        );
+        assert!(result.is_ok());
+        let public_inputs_hash = metrics.stage(Stage::PublicInputsHash);
+        assert_eq!(public_inputs_hash.hash_invocations, 2);
+        assert_eq!(public_inputs_hash.cost.sha256_blocks, 1);
+        assert_eq!(public_inputs_hash.cost.blake3_chunks, 1);
+        assert_eq!(metrics.stage(Stage::VkDigest), Default::default());
+        assert_eq!(
+            metrics.stage(Stage::MerklePath).cost.poseidon2_permutations,
+            valid_proof.vk_merkle_proof.path.len() as u64
+        );
+        for stage in [Stage::Constraints, Stage::Pcs, Stage::Fri] {
+            assert_ne!(metrics.stage(stage), Default::default(), "{stage:?}");
+        }
+        // The estimate follows what the verifier does for the shape of the vk, which is the shape
+        // of a valid proof.
+        assert_eq!(
+            metrics.total().cost,
+            estimate_cost(&valid_proof, valid_inputs.len()).unwrap()
+        );
+    }
+
+    #[rstest]
+    fn count_the_shrink_proof_up_to_the_failing_step(
+        proof_with_invalid_fri_proof: Proof,
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+    ) {
+        let (result, metrics) = verify_instrumented(
+            &NativeBackend::default(),
+            &valid_vk_hash,
+            &proof_with_invalid_fri_proof,
+            &valid_inputs,
+        );
+        assert!(result.is_err());
+        assert_ne!(metrics.stage(Stage::MerklePath), Default::default());
+        // The trace openings all held, then the first FRI round failed.
+        assert_ne!(metrics.stage(Stage::Pcs), Default::default());
+        assert_ne!(metrics.stage(Stage::Fri), Default::default());
+        assert_eq!(metrics.stage(Stage::Constraints), Default::default());
+        let estimate = estimate_cost(&proof_with_invalid_fri_proof, valid_inputs.len()).unwrap();
+        assert!(metrics.total().cost.fits_within(&estimate));
+        assert_ne!(metrics.total().cost, estimate);
+    }
+
+    #[rstest]
+    fn stop_counting_at_the_failing_stage(
+        valid_proof: Proof,
+        invalid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+    ) {
+        let (result, metrics) = verify_instrumented(
+            &NativeBackend::default(),
+            &invalid_vk_hash,
+            &valid_proof,
+            &valid_inputs,
+        );
+        assert!(result.is_err());
+        assert_eq!(metrics.stage(Stage::PublicInputsHash).hash_invocations, 2);
+        for stage in [
+            Stage::PublicValuesDigest,
+            Stage::RecursionVkHash,
+            Stage::MerklePath,
+            Stage::Constraints,
+            Stage::Pcs,
+            Stage::Fri,
+        ] {
+            assert_eq!(metrics.stage(stage), Default::default());
+        }
+    }
+}
+
+mod metered_verifier_should {
+    use super::*;
+    use sp1_zkv_verifier::{MeteredError, Stage, VerificationCost, estimate_cost, verify_metered};
+
+    #[rstest]
+    fn accept_valid_proof_within_budget(
+        valid_proof: Proof,
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+    ) {
+        let budget = estimate_cost(&valid_proof, valid_inputs.len()).unwrap();
+        let spent = verify_metered(
+            &NativeBackend::default(),
+            &valid_vk_hash,
+            &valid_proof,
+            &valid_inputs,
+            budget,
+        )
+        .unwrap();
+        // Counted as the verification ran, and exactly what the estimate predicts from the vk.
+        assert_eq!(spent, budget);
+    }
+
+    #[rstest]
+    fn abort_before_first_stage_with_empty_budget(
+        valid_proof: Proof,
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+    ) {
+        assert!(matches!(
+            verify_metered(
+                &NativeBackend::default(),
+                &valid_vk_hash,
+                &valid_proof,
+                &valid_inputs,
+                VerificationCost::default()
+            ),
+            Err(MeteredError::BudgetExceeded {
+                stage: Stage::PublicInputsHash,
+                ..
+            })
+        ));
+    }
+
+    #[rstest]
+    fn abort_before_last_stage_when_budget_is_one_permutation_short(
+        valid_proof: Proof,
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+    ) {
+        let mut budget = estimate_cost(&valid_proof, valid_inputs.len()).unwrap();
+        budget.poseidon2_permutations -= 1;
+        assert!(matches!(
+            verify_metered(
+                &NativeBackend::default(),
+                &valid_vk_hash,
+                &valid_proof,
+                &valid_inputs,
+                budget
+            ),
+            Err(MeteredError::BudgetExceeded {
+                stage: Stage::Fri,
+                ..
+            })
+        ));
+    }
+
+    #[rstest]
+    fn reject_invalid_proof_within_budget(
+        proof_with_invalid_fri_proof: Proof,
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+    ) {
+        let budget = estimate_cost(&proof_with_invalid_fri_proof, valid_inputs.len()).unwrap();
+        assert!(matches!(
+            verify_metered(
+                &NativeBackend::default(),
+                &valid_vk_hash,
+                &proof_with_invalid_fri_proof,
+                &valid_inputs,
+                budget
+            ),
+            Err(MeteredError::Verification(_))
+        ));
+    }
+
+    #[rstest]
+    fn name_the_stage_exceeding_the_budget(
+        valid_proof: Proof,
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+    ) {
+        let mut budget = estimate_cost(&valid_proof, valid_inputs.len()).unwrap();
+        budget.field_ops = 0;
+        let error = verify_metered(
+            &NativeBackend::default(),
+            &valid_vk_hash,
+            &valid_proof,
+            &valid_inputs,
+            budget,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("Constraints"), "{error}");
+    }
+}
+
+#[cfg(feature = "packed")]
+mod packed_verifier_should {
+    use super::*;
+    use sp1_zkv_verifier::{
+        PackedBackend, recursion_vk_hash, recursion_vk_hashes, verify, verify_batch,
+        verify_with_backend,
+    };
+
+    #[rstest]
+    fn hash_recursion_vks_like_the_scalar_path(valid_proof: Proof) {
+        // Vary the chip names so that lanes absorb inputs of different lengths.
+        let vks: Vec<_> = (0..17)
+            .map(|i| {
+                let mut vk = valid_proof.vk.clone();
+                vk.chip_information[0].0.push_str(&"x".repeat(i));
+                vk
+            })
+            .collect();
+        let expected: Vec<_> = vks
+            .iter()
+            .map(|vk| recursion_vk_hash(&NativeBackend::default(), vk))
+            .collect();
+        assert_eq!(
+            recursion_vk_hashes(&PackedBackend::default(), &vks),
+            expected
+        );
+    }
+
+    #[rstest]
+    fn verify_batches_like_verify(
+        valid_proof: Proof,
+        proof_with_invalid_recursion_vk_merkle_proof: Proof,
+        proof_incomplete: Proof,
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+    ) {
+        let proofs = [
+            valid_proof,
+            proof_with_invalid_recursion_vk_merkle_proof,
+            proof_incomplete,
+        ];
+        let batch: Vec<_> = proofs
+            .iter()
+            .map(|proof| (&valid_vk_hash, proof, valid_inputs.as_slice()))
+            .collect();
+        let results = verify_batch(&PackedBackend::default(), &batch);
+        for (result, proof) in results.iter().zip(&proofs) {
+            assert_eq!(
+                result.is_ok(),
+                verify(&valid_vk_hash, proof, &valid_inputs).is_ok()
+            );
+        }
+        assert!(results[0].is_ok());
+    }
+
+    #[rstest]
+    fn verify_shrink_proofs_on_packed_lanes(
+        valid_proof: Proof,
+        proof_with_invalid_fri_proof: Proof,
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+    ) {
+        let backend = PackedBackend::default();
+        verify_with_backend(&backend, &valid_vk_hash, &valid_proof, &valid_inputs).unwrap();
+        verify_with_backend(
+            &backend,
+            &valid_vk_hash,
+            &proof_with_invalid_fri_proof,
+            &valid_inputs,
+        )
+        .unwrap_err();
+    }
+}
+
+mod verifier_with_backend_should {
+    use super::*;
+    use sp1_zkv_verifier::{HashBackend, NativeBackend, POSEIDON2_WIDTH, verify_with_backend};
+    use std::cell::Cell;
+
+    /// Delegates to [`NativeBackend`] while counting calls.
+    #[derive(Default)]
+    struct CountingBackend {
+        native: NativeBackend,
+        sha256: Cell<usize>,
+        blake3: Cell<usize>,
+        poseidon2: Cell<usize>,
+    }
+
+    impl HashBackend for CountingBackend {
+        fn sha256(&self, data: &[u8]) -> [u8; 32] {
+            self.sha256.set(self.sha256.get() + 1);
+            self.native.sha256(data)
+        }
+
+        fn blake3(&self, data: &[u8]) -> [u8; 32] {
+            self.blake3.set(self.blake3.get() + 1);
+            self.native.blake3(data)
+        }
+
+        fn poseidon2_permute(&self, state: &mut [BabyBear; POSEIDON2_WIDTH]) {
+            self.poseidon2.set(self.poseidon2.get() + 1);
+            self.native.poseidon2_permute(state)
+        }
+    }
+
+    /// Breaks every SHA-256 and BLAKE3 digest.
+    #[derive(Default)]
+    struct BrokenBackend(NativeBackend);
+
+    impl HashBackend for BrokenBackend {
+        fn sha256(&self, _data: &[u8]) -> [u8; 32] {
+            [0; 32]
+        }
+
+        fn blake3(&self, _data: &[u8]) -> [u8; 32] {
+            [0; 32]
+        }
+
+        fn poseidon2_permute(&self, state: &mut [BabyBear; POSEIDON2_WIDTH]) {
+            self.0.poseidon2_permute(state)
+        }
+    }
+
+    #[rstest]
+    fn route_every_hash_through_the_backend(
+        valid_proof: Proof,
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+    ) {
+        let backend = CountingBackend::default();
+        assert!(verify_with_backend(&backend, &valid_vk_hash, &valid_proof, &valid_inputs).is_ok());
+        assert_eq!(backend.sha256.get(), 1);
+        assert_eq!(backend.blake3.get(), 1);
+        assert!(backend.poseidon2.get() > valid_proof.vk_merkle_proof.path.len());
+    }
+
+    #[rstest]
+    fn reject_when_the_backend_disagrees(
+        valid_proof: Proof,
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+    ) {
+        assert!(
+            verify_with_backend(
+                &BrokenBackend::default(),
+                &valid_vk_hash,
+                &valid_proof,
+                &valid_inputs
+            )
+            .is_err()
+        );
+    }
+}
+
+mod statement_should {
+    use super::*;
+    use sp1_zkv_verifier::{
+        HashBackend, HashKind, NativeBackend, claimed_vkey_digest, statement_hash, statement_leaf,
+        verify_statement,
+    };
+
+    #[rstest]
+    fn claim_the_program_vk_digest(valid_proof: Proof, valid_vk_hash: [u8; 32]) {
+        assert_eq!(claimed_vkey_digest(&valid_proof), valid_vk_hash);
+    }
+
+    #[rstest]
+    fn be_returned_by_verification(
+        valid_proof: Proof,
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+    ) {
+        let statement = verify_statement(
+            &NativeBackend::default(),
+            &valid_vk_hash,
+            &valid_proof,
+            &valid_inputs,
+        )
+        .unwrap();
+        let backend = NativeBackend::default();
+        let inputs_hash = match statement.hash_kind {
+            HashKind::Sha256 => backend.sha256(&valid_inputs),
+            HashKind::Blake3 => backend.blake3(&valid_inputs),
+        };
+
+        assert_eq!(statement.vkey_digest, valid_vk_hash);
+        assert_eq!(statement.committed_value_digest, inputs_hash);
+    }
+
+    #[test]
+    fn keep_hash_kind_discriminants() {
+        assert_eq!(HashKind::Sha256 as u8, 0);
+        assert_eq!(HashKind::Blake3 as u8, 1);
+    }
+
+    #[rstest]
+    fn not_be_returned_for_invalid_proof(
+        valid_proof: Proof,
+        valid_vk_hash: [u8; 32],
+        invalid_inputs: Vec<u8>,
+    ) {
+        assert!(
+            verify_statement(
+                &NativeBackend::default(),
+                &valid_vk_hash,
+                &valid_proof,
+                &invalid_inputs
+            )
+            .is_err()
+        );
+    }
+
+    // Computed from the formula of `statement_hash` with OpenSSL's Keccak-256
+    // (`openssl dgst -keccak-256`), independently of this crate.
+    #[rstest]
+    #[case::reference_proof(
+        "45946758049372c74bceb6ba6526a9661b1915a3403125e470d5ff7f6c15c0dd",
+        "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9",
+        "904f415b0560295f89903c3f483ef4d5102335c638ebabd60e947ec5b41a884d",
+        "e35372765810441004e86bf5f1ee78d2aa9cc585362c22b295d8803a413ab925"
+    )]
+    #[case::empty_inputs(
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "",
+        "41a811819c115e346859367316645f58e55bdab450d0ea3a74b474c176fc28ba",
+        "728183d0629b68fe27ec34948a4ec3c1de1faf4fb4bbc9300756042b902939b0"
+    )]
+    #[case::text_inputs(
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        "68656c6c6f20776f726c64",
+        "ec9bdbc1d8c45651e7f94a55bca46606bd4442b23bbf7491b8457b979ab28434",
+        "94b290827423f873577d6b09db149a4576c1967de954dfab0da133b50ff8ee72"
+    )]
+    fn match_reference_vectors(
+        #[case] vk_hash: &str,
+        #[case] inputs: &str,
+        #[case] expected_statement: &str,
+        #[case] expected_leaf: &str,
+    ) {
+        let vk_hash: [u8; 32] = hex::decode(vk_hash).unwrap().try_into().unwrap();
+        let inputs = hex::decode(inputs).unwrap();
+
+        assert_eq!(
+            hex::encode(statement_hash(&vk_hash, &inputs)),
+            expected_statement
+        );
+        assert_eq!(
+            hex::encode(statement_leaf(&vk_hash, &inputs)),
+            expected_leaf
+        );
+    }
+}
+
+#[cfg(feature = "attestation")]
+mod attestation_should {
+    use super::*;
+    use ed25519_dalek::SigningKey;
+    use sp1_zkv_verifier::{verify_and_attest, verify_attestation, verify_statement};
+
+    #[fixture]
+    fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[7; 32])
+    }
+
+    #[rstest]
+    fn sign_the_verified_statement(
+        valid_proof: Proof,
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+        signing_key: SigningKey,
+    ) {
+        let attestation = verify_and_attest(
+            &NativeBackend::default(),
+            &signing_key,
+            &valid_vk_hash,
+            &valid_proof,
+            &valid_inputs,
+            1_700_000_000,
+            42,
+        )
+        .unwrap();
+
+        assert_eq!(
+            attestation.statement,
+            verify_statement(
+                &NativeBackend::default(),
+                &valid_vk_hash,
+                &valid_proof,
+                &valid_inputs
+            )
+            .unwrap()
+        );
+        assert!(verify_attestation(&attestation, &signing_key.verifying_key()).is_ok());
+    }
+
+    #[rstest]
+    fn not_attest_invalid_proof(
+        valid_proof: Proof,
+        invalid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+        signing_key: SigningKey,
+    ) {
+        assert!(
+            verify_and_attest(
+                &NativeBackend::default(),
+                &signing_key,
+                &invalid_vk_hash,
+                &valid_proof,
+                &valid_inputs,
+                1_700_000_000,
+                42
+            )
+            .is_err()
+        );
+    }
+
+    #[rstest]
+    fn reject_tampered_attestation(
+        valid_proof: Proof,
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+        signing_key: SigningKey,
+    ) {
+        let attestation = verify_and_attest(
+            &NativeBackend::default(),
+            &signing_key,
+            &valid_vk_hash,
+            &valid_proof,
+            &valid_inputs,
+            1_700_000_000,
+            42,
+        )
+        .unwrap();
+        let verifying_key = signing_key.verifying_key();
+
+        let mut tampered = attestation.clone();
+        tampered.nonce += 1;
+        assert!(verify_attestation(&tampered, &verifying_key).is_err());
+
+        let mut tampered = attestation.clone();
+        tampered.statement.committed_value_digest[0] ^= 1;
+        assert!(verify_attestation(&tampered, &verifying_key).is_err());
+
+        let other_key = SigningKey::from_bytes(&[8; 32]).verifying_key();
+        assert!(verify_attestation(&attestation, &other_key).is_err());
+    }
+}
+
+mod aggregation_should {
+    use super::*;
+    use sha3::{Digest, Keccak256};
+    use sp1_zkv_verifier::{aggregation_root, is_statement_included};
+
+    fn keccak(data: &[u8]) -> [u8; 32] {
+        Keccak256::digest(data).into()
+    }
+
+    /// The roots of the `should_generate_root_pow_2` and `should_generate_root_complex` tests of
+    /// Substrate's `binary_merkle_tree` (`substrate/utils/binary-merkle-tree` in polkadot-sdk),
+    /// which builds zkVerify's aggregation trees. It hashes every leaf before building the tree.
+    #[test]
+    fn match_binary_merkle_tree_roots() {
+        let a = keccak(&hex::decode("E04CC55ebEE1cBCE552f250e85c57B70B2E2625b").unwrap());
+        let b = keccak(&hex::decode("25451A4de12dcCc2D166922fA938E900fCc4ED24").unwrap());
+        let root = "697ea2a8fe5b03468548a7a413424a6292ab44a82a6f5cc594c3fa7dda7ce402";
+        assert_eq!(hex::encode(aggregation_root(a, &[b], 0, 2).unwrap()), root);
+        assert_eq!(hex::encode(aggregation_root(b, &[a], 1, 2).unwrap()), root);
+
+        let [a, b, c] = [b"a", b"b", b"c"].map(|leaf| keccak(leaf));
+        let root = "aff1208e69c9e8be9b584b07ebac4e48a1ee9d15ce3afe20b77a4d29e4175aa3";
+        assert_eq!(
+            hex::encode(aggregation_root(a, &[b, c], 0, 3).unwrap()),
+            root
+        );
+        // `c` is promoted past the first level, where it has no sibling.
+        let ab = keccak(&[a, b].concat());
+        assert_eq!(hex::encode(aggregation_root(c, &[ab], 2, 3).unwrap()), root);
+    }
+
+    fn decode_path(path: &[&str]) -> Vec<[u8; 32]> {
+        path.iter()
+            .map(|node| hex::decode(node).unwrap().try_into().unwrap())
+            .collect()
+    }
+
+    fn root_with_leaf_at_2() -> [u8; 32] {
+        hex::decode("20e7f90c1b0676249de09798a7afdaa57c76e5d640dd04cdb7713932841873f9")
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    fn path_of_leaf_at_2() -> Vec<[u8; 32]> {
+        decode_path(&[
+            "91db6cbfa8475611ccc9a2128c04529ac158e4d841cb5dccb8e09f0c9d4e6717",
+            "4ccd92bfc570810e4185ca54ed2596fbbc5b146e6d182c7b2d5a9c7ea5338906",
+            "a160f64ebdf62c6a42aa5e8a11303cf79f2c77be84c8c3a38162be0a4bef7c0a",
+        ])
+    }
+
+    #[rstest]
+    #[case::inner_leaf(path_of_leaf_at_2(), 2, root_with_leaf_at_2())]
+    #[case::promoted_leaf(
+        decode_path(&["a80a2d0cdc78583e42097a1c277f70dcaa256c84c406a435bcc198df55789270"]),
+        4,
+        hex::decode("8aebf6236fded77fcea826a3a4f332fc5c09cd5c59b05c7cb2faa7e53931a1dd")
+            .unwrap()
+            .try_into()
+            .unwrap()
+    )]
+    fn accept_included_statement(
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+        #[case] path: Vec<[u8; 32]>,
+        #[case] leaf_index: usize,
+        #[case] root: [u8; 32],
+    ) {
+        assert!(is_statement_included(
+            &valid_vk_hash,
+            &valid_inputs,
+            &path,
+            leaf_index,
+            5,
+            &root
+        ));
+    }
+
+    #[rstest]
+    #[case::wrong_index(path_of_leaf_at_2(), 3, 5)]
+    #[case::wrong_number_of_leaves(path_of_leaf_at_2(), 2, 4)]
+    #[case::index_out_of_range(path_of_leaf_at_2(), 5, 5)]
+    #[case::truncated_path(path_of_leaf_at_2()[..2].to_vec(), 2, 5)]
+    #[case::extended_path([path_of_leaf_at_2(), vec![[0; 32]]].concat(), 2, 5)]
+    fn reject_invalid_receipt(
+        valid_vk_hash: [u8; 32],
+        valid_inputs: Vec<u8>,
+        #[case] path: Vec<[u8; 32]>,
+        #[case] leaf_index: usize,
+        #[case] number_of_leaves: usize,
+    ) {
+        assert!(!is_statement_included(
+            &valid_vk_hash,
+            &valid_inputs,
+            &path,
+            leaf_index,
+            number_of_leaves,
+            &root_with_leaf_at_2()
+        ));
+    }
+
+    #[rstest]
+    fn reject_other_statement(invalid_vk_hash: [u8; 32], invalid_inputs: Vec<u8>) {
+        let path = path_of_leaf_at_2();
+        let root = root_with_leaf_at_2();
+        assert!(!is_statement_included(
+            &invalid_vk_hash,
+            &invalid_inputs,
+            &path,
+            2,
+            5,
+            &root
+        ));
+    }
 }